@@ -0,0 +1,258 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use halo2::{
+    circuit::{layouter::TableLayouter, Cell, Layouter, Region, SimpleFloorPlanner, Table},
+    dev::MockProver,
+    plonk::{self, Advice, Assigned, Column, ConstraintSystem, Error, Instance, TableColumn},
+};
+use pasta_curves::pallas;
+use std::convert::TryInto;
+
+use xors::gadget::{
+    bitwise::{Bitwise32Chip, Bitwise32Config, BitwiseInstructions},
+    decompose::{Decompose32Chip, Decompose32Config, DecomposeInstruction},
+    fused_xor::{FusedXor32Chip, FusedXor32Config},
+    xor::{TableChip, TableConfig},
+};
+use xors::word::{AssignedWord32, Word32};
+
+/// Layouter that only assigns tables and keeps their values, without the rows of a whole circuit
+#[derive(Debug, Default)]
+struct TableAssignment {
+    cells: Vec<(TableColumn, usize, Assigned<pallas::Base>)>,
+}
+
+impl TableLayouter<pallas::Base> for TableAssignment {
+    fn assign_cell<'v>(
+        &'v mut self,
+        _: &'v (dyn Fn() -> String + 'v),
+        column: TableColumn,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Result<Assigned<pallas::Base>, Error> + 'v),
+    ) -> Result<(), Error> {
+        self.cells.push((column, offset, to()?));
+        Ok(())
+    }
+}
+
+impl Layouter<pallas::Base> for TableAssignment {
+    type Root = Self;
+
+    fn assign_region<A, AR, N, NR>(&mut self, _: N, _: A) -> Result<AR, Error>
+    where
+        A: FnMut(Region<'_, pallas::Base>) -> Result<AR, Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        Err(Error::Synthesis)
+    }
+
+    fn assign_table<A, N, NR>(&mut self, _: N, mut assignment: A) -> Result<(), Error>
+    where
+        A: FnMut(Table<'_, pallas::Base>) -> Result<(), Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        let table: &mut dyn TableLayouter<pallas::Base> = self;
+        assignment(table.into())
+    }
+
+    fn constrain_instance(&mut self, _: Cell, _: Column<Instance>, _: usize) -> Result<(), Error> {
+        Err(Error::Synthesis)
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+/// Decomposes every word
+#[derive(Debug, Default)]
+struct DecomposeCircuit {
+    words: Vec<Option<Word32>>,
+}
+
+impl plonk::Circuit<pallas::Base> for DecomposeCircuit {
+    type Config = (Column<Advice>, Decompose32Config);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        DecomposeCircuit {
+            words: vec![None; self.words.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        let advice = [meta.advice_column(), meta.advice_column(), meta.advice_column()];
+        (advice[0], Decompose32Chip::configure(meta, advice))
+    }
+
+    fn synthesize(
+        &self,
+        (advice, config): Self::Config,
+        mut layouter: impl Layouter<pallas::Base>,
+    ) -> Result<(), Error> {
+        let chip = Decompose32Chip::<pallas::Base>::construct(config);
+
+        for word in self.words.iter() {
+            let word = AssignedWord32::assign_word(layouter.namespace(|| "word"), advice, *word)?;
+            chip.decompose(layouter.namespace(|| "decompose"), word)?;
+        }
+        Ok(())
+    }
+}
+
+/// Xors every pair the way `BitwiseChip` does: load and decompose both words, look up the chunks
+/// and compose the result
+#[derive(Debug, Default)]
+struct BitwiseXorCircuit {
+    pairs: Vec<(Option<Word32>, Option<Word32>)>,
+}
+
+impl plonk::Circuit<pallas::Base> for BitwiseXorCircuit {
+    type Config = Bitwise32Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        BitwiseXorCircuit {
+            pairs: vec![(None, None); self.pairs.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        let advice: Vec<Column<Advice>> = (0..6).map(|_| meta.advice_column()).collect();
+        let constants = meta.fixed_column();
+
+        let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+        let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+        Bitwise32Chip::<pallas::Base>::configure(meta, advice[0..3].try_into().unwrap(), constants, decompose_config, table_config)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<pallas::Base>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.table_config.clone(), &mut layouter)?;
+        let chip = Bitwise32Chip::<pallas::Base>::construct(config);
+
+        for (x, y) in self.pairs.iter() {
+            let x = chip.load_word(layouter.namespace(|| "load x"), *x)?;
+            let y = chip.load_word(layouter.namespace(|| "load y"), *y)?;
+            chip.xor(layouter.namespace(|| "xor"), &x, &y)?;
+        }
+        Ok(())
+    }
+}
+
+/// Xors every pair in a single `FusedXorChip` region
+#[derive(Debug, Default)]
+struct FusedXorCircuit {
+    pairs: Vec<(Option<Word32>, Option<Word32>)>,
+}
+
+impl plonk::Circuit<pallas::Base> for FusedXorCircuit {
+    type Config = FusedXor32Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        FusedXorCircuit {
+            pairs: vec![(None, None); self.pairs.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        let advice: Vec<Column<Advice>> = (0..4).map(|_| meta.advice_column()).collect();
+
+        let table_config = TableChip::configure(meta, advice[0], advice[1], advice[2]);
+        FusedXor32Chip::<pallas::Base>::configure(meta, advice[3], table_config)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<pallas::Base>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.table_config.clone(), &mut layouter)?;
+        let chip = FusedXor32Chip::<pallas::Base>::construct(config);
+
+        for (x, y) in self.pairs.iter() {
+            chip.witness_xor(layouter.namespace(|| "xor"), *x, *y)?;
+        }
+        Ok(())
+    }
+}
+
+fn pairs(len: u64) -> Vec<(Option<Word32>, Option<Word32>)> {
+    (0..len)
+        .map(|i| {
+            let x = 0x9e3779b9u64.wrapping_mul(i) & 0xffffffff;
+            (Some(Word32::new(x)), Some(Word32::new(x ^ i)))
+        })
+        .collect()
+}
+
+fn table(c: &mut Criterion) {
+    let mut group = c.benchmark_group("TableChip::load");
+    group.sample_size(10);
+
+    let mut meta = ConstraintSystem::<pallas::Base>::default();
+    let advice = [meta.advice_column(), meta.advice_column(), meta.advice_column()];
+    let config: TableConfig = TableChip::configure(&mut meta, advice[0], advice[1], advice[2]);
+
+    group.bench_function("assign xor table", |b| {
+        b.iter(|| {
+            let mut layouter = TableAssignment::default();
+            TableChip::load(config.clone(), &mut layouter).unwrap();
+            layouter
+        })
+    });
+
+    group.finish();
+}
+
+fn decompose(c: &mut Criterion) {
+    let mut group = c.benchmark_group("DecomposeChip::decompose");
+
+    for words in [1, 16, 256] {
+        let circuit = DecomposeCircuit {
+            words: (0..words).map(|i| Some(Word32::new(0x9e3779b9u64.wrapping_mul(i) & 0xffffffff))).collect(),
+        };
+
+        group.bench_with_input(BenchmarkId::from_parameter(words), &circuit, |b, circuit| {
+            b.iter(|| MockProver::run(11, circuit, vec![]).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+fn word_xor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("word xor");
+    group.sample_size(10);
+
+    for len in [16, 256, 4096] {
+        let bitwise = BitwiseXorCircuit { pairs: pairs(len) };
+        let fused = FusedXorCircuit { pairs: pairs(len) };
+
+        group.bench_with_input(BenchmarkId::new("bitwise", len), &bitwise, |b, circuit| {
+            b.iter(|| MockProver::run(17, circuit, vec![]).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("fused", len), &fused, |b, circuit| {
+            b.iter(|| MockProver::run(17, circuit, vec![]).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, table, decompose, word_xor);
+criterion_main!(benches);
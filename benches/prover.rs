@@ -0,0 +1,217 @@
+use criterion::{criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion};
+use halo2::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    pasta::{EqAffine, Fp},
+    plonk::{self, Advice, Column, ConstraintSystem, Error, Instance, Selector, TableColumn},
+    poly::{commitment::Params, Rotation},
+};
+
+use xors::circuit::{self, BatchXorCircuit, Circuit};
+use xors::cost;
+use xors::gadget::lookup::LookupTable;
+use xors::prover::{self, Setup};
+
+fn xor_entry<const WIDTH: usize>(row: usize) -> [u64; 3] {
+    let (l, r) = ((row >> WIDTH) as u64, (row & ((1 << WIDTH) - 1)) as u64);
+    [l, r, l ^ r]
+}
+
+#[derive(Clone, Debug)]
+struct ChunkedXorConfig {
+    advice: [Column<Advice>; 3],
+    acc: Column<Advice>,
+    instance: Column<Instance>,
+    q_first: Selector,
+    q_acc: Selector,
+    table: [TableColumn; 3],
+}
+
+/// Xors two words in `WIDTH` bit chunks looked up in a `2^(2 * WIDTH)` row table, `z` is
+/// accumulated from its chunks into the public output. The xor circuit itself only has 8 bit
+/// chunks, `Chunk` and the decompose gate are byte wide.
+#[derive(Clone, Copy, Debug, Default)]
+struct ChunkedXorCircuit<const WIDTH: usize> {
+    x: Option<u32>,
+    y: Option<u32>,
+}
+
+impl<const WIDTH: usize> ChunkedXorCircuit<WIDTH> {
+    const TABLE: LookupTable = LookupTable {
+        name: "xor table",
+        len: 1 << (2 * WIDTH),
+        row: xor_entry::<WIDTH>,
+    };
+}
+
+impl<const WIDTH: usize> plonk::Circuit<Fp> for ChunkedXorCircuit<WIDTH> {
+    type Config = ChunkedXorConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        let advice = [meta.advice_column(), meta.advice_column(), meta.advice_column()];
+        let acc = meta.advice_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(acc.into());
+        meta.enable_equality(instance.into());
+
+        let q_first = meta.selector();
+        let q_acc = meta.selector();
+        let table = Self::TABLE.configure(meta, advice, None);
+
+        // acc = acc_prev * 2^WIDTH + z, most significant chunk first
+        meta.create_gate("accumulate z", |meta| {
+            let q_first = meta.query_selector(q_first);
+            let q_acc = meta.query_selector(q_acc);
+            let z = meta.query_advice(advice[2], Rotation::cur());
+            let acc_cur = meta.query_advice(acc, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+
+            vec![
+                q_first * (acc_cur.clone() - z.clone()),
+                q_acc * (acc_cur - acc_prev * Fp::from(1 << WIDTH) - z),
+            ]
+        });
+
+        ChunkedXorConfig { advice, acc, instance, q_first, q_acc, table }
+    }
+
+    fn synthesize(&self, config: Self::Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+        Self::TABLE.load(&mut layouter, config.table)?;
+
+        let z = layouter.assign_region(
+            || "xor",
+            |mut region| {
+                let mut acc = None;
+                for row in 0..32 / WIDTH {
+                    let shift = 32 - WIDTH * (row + 1);
+                    let chunk = |word: Option<u32>| word.map(|word| (word >> shift) as u64 & ((1 << WIDTH) - 1));
+                    let (x, y) = (chunk(self.x), chunk(self.y));
+                    let z = x.zip(y).map(|(x, y)| x ^ y);
+
+                    for (column, value) in config.advice.iter().zip([x, y, z]) {
+                        region.assign_advice(|| format!("chunk {}", row), *column, row, || value.map(Fp::from).ok_or(Error::Synthesis))?;
+                    }
+
+                    if row == 0 {
+                        config.q_first.enable(&mut region, row)?;
+                    } else {
+                        config.q_acc.enable(&mut region, row)?;
+                    }
+                    let value = self.x.zip(self.y).map(|(x, y)| ((x ^ y) >> shift) as u64);
+                    acc = Some(region.assign_advice(|| format!("acc {}", row), config.acc, row, || value.map(Fp::from).ok_or(Error::Synthesis))?);
+                }
+                Ok(acc.unwrap())
+            },
+        )?;
+
+        layouter.constrain_instance(z.cell(), config.instance, 0)
+    }
+}
+
+fn xor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("xor circuit");
+    group.sample_size(10);
+
+    let (x, y) = (0xdeadbeef, 0x01234567);
+    let public_inputs = Circuit::public_inputs(x ^ y);
+
+    // chunk widths are in `chunk_width`
+    for k in [circuit::K, circuit::K + 1] {
+        let params = Params::<EqAffine>::new(k);
+
+        group.bench_with_input(BenchmarkId::new("keygen", k), &params, |b, params| {
+            b.iter(|| prover::keygen(params, &Circuit::default()).unwrap())
+        });
+
+        let pk = prover::keygen(&params, &Circuit::default()).unwrap();
+        let setup = Setup { params, pk };
+
+        group.bench_with_input(BenchmarkId::new("prove", k), &setup, |b, setup| {
+            b.iter(|| setup.prove(x, y).unwrap())
+        });
+
+        let proof = setup.prove(x, y).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("verify", k), &setup, |b, setup| {
+            b.iter(|| assert!(setup.verify(&proof, &public_inputs)))
+        });
+    }
+
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch xor circuit");
+    group.sample_size(10);
+
+    for len in [1, 64, 1024] {
+        let pairs: Vec<(u32, u32)> = (0..len).map(|i| (0x9e3779b9u32.wrapping_mul(i), i)).collect();
+        let z: Vec<u32> = pairs.iter().map(|(x, y)| x ^ y).collect();
+        let public_inputs = BatchXorCircuit::public_inputs(&z);
+
+        let circuit = BatchXorCircuit::with_len(pairs.len());
+        let params = Params::<EqAffine>::new(cost::min_k(&circuit).unwrap());
+
+        group.bench_with_input(BenchmarkId::new("keygen", len), &params, |b, params| {
+            b.iter(|| prover::keygen(params, &circuit).unwrap())
+        });
+
+        let pk = prover::keygen(&params, &circuit).unwrap();
+        let setup = Setup { params, pk };
+
+        group.bench_with_input(BenchmarkId::new("prove", len), &setup, |b, setup| {
+            b.iter(|| prover::prove(&setup.params, &setup.pk, BatchXorCircuit::new(&pairs), &[&public_inputs]).unwrap())
+        });
+
+        let proof = prover::prove(&setup.params, &setup.pk, BatchXorCircuit::new(&pairs), &[&public_inputs]).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("verify", len), &setup, |b, setup| {
+            b.iter(|| assert!(setup.verify(&proof, &public_inputs)))
+        });
+    }
+
+    group.finish();
+}
+
+fn chunked_xor<const WIDTH: usize>(group: &mut BenchmarkGroup<'_, WallTime>) {
+    let (x, y) = (0xdeadbeef, 0x01234567);
+    let circuit = ChunkedXorCircuit::<WIDTH> { x: Some(x), y: Some(y) };
+    let public_inputs = [Fp::from((x ^ y) as u64)];
+
+    let params = Params::<EqAffine>::new(cost::min_k(&circuit).unwrap());
+
+    group.bench_with_input(BenchmarkId::new("keygen", WIDTH), &params, |b, params| {
+        b.iter(|| prover::keygen(params, &circuit).unwrap())
+    });
+
+    let pk = prover::keygen(&params, &circuit).unwrap();
+    let setup = Setup { params, pk };
+
+    group.bench_with_input(BenchmarkId::new("prove", WIDTH), &setup, |b, setup| {
+        b.iter(|| prover::prove(&setup.params, &setup.pk, circuit, &[&public_inputs]).unwrap())
+    });
+
+    let proof = prover::prove(&setup.params, &setup.pk, circuit, &[&public_inputs]).unwrap();
+
+    group.bench_with_input(BenchmarkId::new("verify", WIDTH), &setup, |b, setup| {
+        b.iter(|| assert!(setup.verify(&proof, &public_inputs)))
+    });
+}
+
+/// A 4 bit table has 2^8 rows, an 8 bit one 2^16, each at the smallest k it fits
+fn chunk_width(c: &mut Criterion) {
+    let mut group = c.benchmark_group("xor chunk width");
+    group.sample_size(10);
+
+    chunked_xor::<4>(&mut group);
+    chunked_xor::<8>(&mut group);
+
+    group.finish();
+}
+
+criterion_group!(benches, xor, batch, chunk_width);
+criterion_main!(benches);
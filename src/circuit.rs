@@ -1,33 +1,57 @@
 use halo2::{
     circuit::{Layouter, SimpleFloorPlanner},
-    plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+    plonk::{Instance, Column, ConstraintSystem, Error},
     plonk,
-    pasta::Fp
 };
-use std::marker::PhantomData;
+use std::convert::TryInto;
 use pasta_curves::pallas;
 
 
-use crate::word::{Word, Chunk, AssignedChunk, AssignedWord};
+use crate::word::{Chunk, Endianness, Word32};
 use crate::gadget::{
-    decompose::{DecomposeChip, DecomposeConfig, DecomposeInstruction},
+    bytes::{AssignedBytes, BytesChip, BytesConfig},
     xor::{TableChip, TableConfig}
 };
 
+/// The xor table alone takes 2^16 rows
+pub const K: u32 = 17;
+
+/// Identifies this circuit in file headers
+pub const ID: u32 = 1;
 
 #[derive(Clone, Debug)]
 pub struct Config {
-    advice: [Column<Advice>; 6],
-    // instance: Column<Instance>,
-    table_config: TableConfig, 
-    decompose_config: DecomposeConfig
+    instance: Column<Instance>,
+    table_config: TableConfig,
+    bytes_config: BytesConfig,
 }
 
 
+/// Knowledge of `x` and `y` with `x ^ y = z`, where `z` is the only public input.
+/// Every byte is range checked by its xor table row, which keeps the circuit low degree.
 #[derive(Clone, Debug, Default)]
 pub struct Circuit {
-    x: Option<Word>,
-    y: Option<Word>
+    x: Option<Word32>,
+    y: Option<Word32>
+}
+
+impl Circuit {
+    pub fn new(x: u32, y: u32) -> Self {
+        Circuit {
+            x: Some(Word32::new(x as u64)),
+            y: Some(Word32::new(y as u64)),
+        }
+    }
+
+    pub fn public_inputs(z: u32) -> Vec<pallas::Base> {
+        vec![pallas::Base::from(z as u64)]
+    }
+
+    fn bytes(word: Option<Word32>) -> Vec<Option<u8>> {
+        (0..4)
+            .map(|i| word.map(|word| (*word >> (8 * i)) as u8))
+            .collect()
+    }
 }
 
 
@@ -47,24 +71,19 @@ impl plonk::Circuit<pallas::Base> for Circuit {
             meta.advice_column(),
             meta.advice_column(),
             meta.advice_column(),
-            meta.advice_column(),
         ];
+        let constants = meta.fixed_column();
 
-        // let instance = meta.instance_column();
-        // meta.enable_equality(instance.into());
-
-        for advice in advice.iter() {
-            meta.enable_equality((*advice).into());
-        }
+        let instance = meta.instance_column();
+        meta.enable_equality(instance.into());
 
-        let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
-        let decompose_config = DecomposeChip::configure(meta, advice[0..3].try_into().unwrap());
+        let table_config = TableChip::configure(meta, advice[0], advice[1], advice[2]);
+        let bytes_config = BytesChip::configure(meta, advice[3..5].try_into().unwrap(), constants, table_config.clone());
 
         Config {
-            advice, 
-            // instance,
+            instance,
             table_config,
-            decompose_config
+            bytes_config
         }
     }
 
@@ -73,47 +92,107 @@ impl plonk::Circuit<pallas::Base> for Circuit {
         config: Self::Config,
         mut layouter: impl Layouter<pallas::Base>,
     ) -> Result<(), Error> {
-        let config = config.clone();
-
         TableChip::load(config.table_config.clone(), &mut layouter)?;
         let table_chip = TableChip::<pallas::Base>::construct(config.table_config.clone());
-        let decompose_chip = DecomposeChip::<pallas::Base>::construct(config.decompose_config.clone());
+        let bytes_chip = BytesChip::<pallas::Base>::construct(config.bytes_config.clone());
+
+        let x = bytes_chip.load_bytes(layouter.namespace(|| "x"), &Self::bytes(self.x))?;
+        let y = bytes_chip.load_bytes(layouter.namespace(|| "y"), &Self::bytes(self.y))?;
+
+        let z = layouter.assign_region(
+            || "xor",
+            |mut region| {
+                x.bytes()
+                    .iter()
+                    .zip(y.bytes())
+                    .enumerate()
+                    .map(|(row, (x, y))| table_chip.xor_row(&mut region, row, x, y))
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?;
 
-        let x = AssignedWord::assign_word(layouter.namespace(|| "witness value"), config.advice[0], self.x).unwrap();
-        let y = AssignedWord::assign_word(layouter.namespace(|| "witness value"), config.advice[0], self.y).unwrap();
+        let z = bytes_chip.pack(layouter.namespace(|| "pack z"), &AssignedBytes::from(z), Endianness::Little)?;
 
-        let (x0, x1, x2, x3) = decompose_chip.decompose(layouter.namespace(|| "decompose x"), x)?;
-        let (y0, y1, y2, y3) = decompose_chip.decompose(layouter.namespace(|| "decompose y"), y)?;
+        layouter.constrain_instance(z.cell(), config.instance, 0)
+    }
+}
 
-        let z0 = x0.value_chunk().zip(y0.value_chunk())
-                .map(|(x0, y0)| Chunk::new(*x0 ^ *y0));
+/// Knowledge of pairs `(x, y)` with `x ^ y = z` for every pair, the `z` are the public inputs in the order of the pairs.
+/// The lookup rows of all pairs share one region, four rows per pair.
+#[derive(Clone, Debug, Default)]
+pub struct BatchXorCircuit {
+    pairs: Vec<(Option<Word32>, Option<Word32>)>,
+}
 
-        let z1 = x1.value_chunk().zip(y1.value_chunk())
-                .map(|(x1, y1)| Chunk::new(*x1 ^ *y1));
+impl BatchXorCircuit {
+    pub fn new(pairs: &[(u32, u32)]) -> Self {
+        BatchXorCircuit {
+            pairs: pairs
+                .iter()
+                .map(|(x, y)| (Some(Word32::new(*x as u64)), Some(Word32::new(*y as u64))))
+                .collect(),
+        }
+    }
 
-        let z2 = x2.value_chunk().zip(y2.value_chunk())
-                .map(|(x2, y2)| Chunk::new(*x2 ^ *y2));
+    /// Circuit of `len` pairs without witnesses, for keygen
+    pub fn with_len(len: usize) -> Self {
+        BatchXorCircuit {
+            pairs: vec![(None, None); len],
+        }
+    }
 
-        let z3 = x3.value_chunk().zip(y3.value_chunk())
-                .map(|(x3, y3)| Chunk::new(*x3 ^ *y3));
+    pub fn public_inputs(z: &[u32]) -> Vec<pallas::Base> {
+        z.iter().map(|z| pallas::Base::from(*z as u64)).collect()
+    }
+}
 
+impl plonk::Circuit<pallas::Base> for BatchXorCircuit {
+    type Config = Config;
+    type FloorPlanner = SimpleFloorPlanner;
 
-        //TOOD add simple iteration through chunks
-        layouter.assign_region(
-            || "compress",
+    fn without_witnesses(&self) -> Self {
+        Self::with_len(self.pairs.len())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        <Circuit as plonk::Circuit<pallas::Base>>::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<pallas::Base>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.table_config.clone(), &mut layouter)?;
+        let table_chip = TableChip::<pallas::Base>::construct(config.table_config.clone());
+        let bytes_chip = BytesChip::<pallas::Base>::construct(config.bytes_config.clone());
+
+        let z = layouter.assign_region(
+            || "xor",
             |mut region| {
-                table_chip.add_row(
-                    &mut region, 
-                    0, 
-                    x0.value_chunk(), 
-                    y0.value_chunk(), 
-                    z0
-                )?;
-                Ok(())
+                self.pairs
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (x, y))| {
+                        Circuit::bytes(*x)
+                            .into_iter()
+                            .zip(Circuit::bytes(*y))
+                            .enumerate()
+                            .map(|(j, (x, y))| {
+                                let x = x.map(|x| Chunk::new(x as u64));
+                                let y = y.map(|y| Chunk::new(y as u64));
+                                table_chip.witness_xor_row(&mut region, 4 * i + j, x, y)
+                            })
+                            .collect::<Result<Vec<_>, Error>>()
+                    })
+                    .collect::<Result<Vec<_>, Error>>()
             },
         )?;
 
-        println!("{:?}, {:?}, {:?}, {:?}", z0, z1, z2, z3);
+        for (i, z) in z.into_iter().enumerate() {
+            let z = bytes_chip.pack(layouter.namespace(|| format!("pack z {}", i)), &AssignedBytes::from(z), Endianness::Little)?;
+            layouter.constrain_instance(z.cell(), config.instance, i)?;
+        }
 
         Ok(())
     }
@@ -124,18 +203,55 @@ mod test {
     use halo2::{
         dev::MockProver,
     };
-    use super::{Circuit};
+    use rand::Rng;
 
-    use crate::word::{Word};
+    use super::{BatchXorCircuit, Circuit, K};
 
     #[test]
     fn main_circuit() {
-        let circuit = Circuit {
-            x: Some(Word::new(0b10101010)),
-            y: Some(Word::new(0b01010101))
-        };
-        let k = 17;
-        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        let (x, y) = (0xdeadbeef, 0x01234567);
+        let circuit = Circuit::new(x, y);
+
+        let prover = MockProver::run(K, &circuit, vec![Circuit::public_inputs(x ^ y)]).unwrap();
         assert_eq!(prover.verify(), Ok(()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn main_circuit_wrong_output() {
+        let (x, y) = (0xdeadbeef, 0x01234567);
+        let circuit = Circuit::new(x, y);
+
+        let prover = MockProver::run(K, &circuit, vec![Circuit::public_inputs(x ^ y ^ 1)]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn batch_circuit() {
+        let mut rng = rand::thread_rng();
+        let pairs: Vec<(u32, u32)> = (0..1000).map(|_| (rng.gen(), rng.gen())).collect();
+        let z: Vec<u32> = pairs.iter().map(|(x, y)| x ^ y).collect();
+
+        let prover = MockProver::run(K, &BatchXorCircuit::new(&pairs), vec![BatchXorCircuit::public_inputs(&z)]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn batch_circuit_wrong_output() {
+        let mut rng = rand::thread_rng();
+        let pairs: Vec<(u32, u32)> = (0..64).map(|_| (rng.gen(), rng.gen())).collect();
+
+        for i in [0, 31, 63] {
+            let mut z: Vec<u32> = pairs.iter().map(|(x, y)| x ^ y).collect();
+            z[i] ^= 1 << rng.gen_range(0..32);
+
+            let prover = MockProver::run(K, &BatchXorCircuit::new(&pairs), vec![BatchXorCircuit::public_inputs(&z)]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+
+        // outputs in another order do not match their pairs
+        let mut z: Vec<u32> = pairs.iter().map(|(x, y)| x ^ y).collect();
+        z.swap(0, 1);
+        let prover = MockProver::run(K, &BatchXorCircuit::new(&pairs), vec![BatchXorCircuit::public_inputs(&z)]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,487 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{
+        layouter::{RegionColumn, RegionLayouter, RegionShape, TableLayouter},
+        Cell, Layouter, Region, Table,
+    },
+    dev::CircuitCost,
+    plonk::{self, Advice, Assigned, Column, ConstraintSystem, Error, Fixed, Instance, Selector, TableColumn},
+};
+use pasta_curves::{pallas, vesta};
+use std::{
+    any, cmp,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    marker::PhantomData,
+};
+
+/// Number of regions laid out and the rows they take
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegionCost {
+    pub count: usize,
+    pub rows: usize,
+}
+
+/// Chip that lays out a region, the last type in the path of the region's assignment closure:
+/// `xors::gadget::bytes::BytesChip<F>::pack::{{closure}}` is `BytesChip`, and so is the closure
+/// of a trait method `<BytesChip<F> as Trait<F>>::method::{{closure}}`.
+/// A closure outside any type falls back to its function.
+fn chip_of<A>() -> &'static str {
+    let name = any::type_name::<A>();
+    let path = name.trim_start_matches('<');
+    let path = &path[..path.find(['<', ' ']).unwrap_or(path.len())];
+
+    let segments = path.split("::").filter(|segment| !segment.starts_with('{'));
+    segments
+        .clone()
+        .filter(|segment| segment.starts_with(char::is_uppercase))
+        .last()
+        .or_else(|| segments.last())
+        .unwrap_or(name)
+}
+
+/// Layouter that only records region shapes and places them like `SimpleFloorPlanner`
+#[derive(Debug)]
+pub struct RecordingLayouter<F> {
+    regions: BTreeMap<String, RegionCost>,
+    chips: BTreeMap<&'static str, RegionCost>,
+    region_count: usize,
+    /// first unused row of every column
+    columns: HashMap<RegionColumn, usize>,
+    table_columns: HashSet<TableColumn>,
+    table_rows: usize,
+    constants: usize,
+    copies: usize,
+    _marker: PhantomData<F>,
+}
+
+#[derive(Debug)]
+struct RecordingRegion {
+    shape: RegionShape,
+    constants: usize,
+    copies: usize,
+}
+
+#[derive(Debug, Default)]
+struct RecordingTable {
+    columns: HashSet<TableColumn>,
+    rows: usize,
+}
+
+impl<F: FieldExt> RegionLayouter<F> for RecordingRegion {
+    fn enable_selector<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        selector: &Selector,
+        offset: usize,
+    ) -> Result<(), Error> {
+        RegionLayouter::<F>::enable_selector(&mut self.shape, annotation, selector, offset)
+    }
+
+    fn assign_advice<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Advice>,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Result<Assigned<F>, Error> + 'v),
+    ) -> Result<Cell, Error> {
+        self.shape.assign_advice(annotation, column, offset, to)
+    }
+
+    fn assign_advice_from_constant<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Advice>,
+        offset: usize,
+        constant: Assigned<F>,
+    ) -> Result<Cell, Error> {
+        self.constants += 1;
+        self.shape.assign_advice_from_constant(annotation, column, offset, constant)
+    }
+
+    fn assign_advice_from_instance<'v>(
+        &mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        instance: Column<Instance>,
+        row: usize,
+        advice: Column<Advice>,
+        offset: usize,
+    ) -> Result<(Cell, Option<F>), Error> {
+        self.shape.assign_advice_from_instance(annotation, instance, row, advice, offset)
+    }
+
+    fn assign_fixed<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Fixed>,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Result<Assigned<F>, Error> + 'v),
+    ) -> Result<Cell, Error> {
+        self.shape.assign_fixed(annotation, column, offset, to)
+    }
+
+    fn constrain_constant(&mut self, cell: Cell, constant: Assigned<F>) -> Result<(), Error> {
+        self.constants += 1;
+        self.shape.constrain_constant(cell, constant)
+    }
+
+    fn constrain_equal(&mut self, left: Cell, right: Cell) -> Result<(), Error> {
+        self.copies += 1;
+        RegionLayouter::<F>::constrain_equal(&mut self.shape, left, right)
+    }
+}
+
+impl<F: FieldExt> TableLayouter<F> for RecordingTable {
+    fn assign_cell<'v>(
+        &'v mut self,
+        _: &'v (dyn Fn() -> String + 'v),
+        column: TableColumn,
+        offset: usize,
+        _: &'v mut (dyn FnMut() -> Result<Assigned<F>, Error> + 'v),
+    ) -> Result<(), Error> {
+        self.columns.insert(column);
+        self.rows = cmp::max(self.rows, offset + 1);
+        Ok(())
+    }
+}
+
+impl<F: FieldExt> RecordingLayouter<F> {
+    /// Configures `circuit` and synthesizes it without witnesses
+    pub fn record<C: plonk::Circuit<F>>(circuit: &C) -> Result<(ConstraintSystem<F>, Self), Error> {
+        let mut meta = ConstraintSystem::default();
+        let config = C::configure(&mut meta);
+
+        let mut layouter = RecordingLayouter {
+            regions: BTreeMap::new(),
+            chips: BTreeMap::new(),
+            region_count: 0,
+            columns: HashMap::new(),
+            table_columns: HashSet::new(),
+            table_rows: 0,
+            constants: 0,
+            copies: 0,
+            _marker: PhantomData,
+        };
+        circuit.without_witnesses().synthesize(config, layouter.namespace(|| "circuit"))?;
+
+        Ok((meta, layouter))
+    }
+
+    /// Rows taken by regions, tables and constants
+    pub fn rows(&self) -> usize {
+        cmp::max(cmp::max(self.region_rows(), self.table_rows), self.constants)
+    }
+
+    /// Rows taken by regions alone
+    pub fn region_rows(&self) -> usize {
+        self.columns.values().copied().max().unwrap_or(0)
+    }
+
+    /// Copy constraints between cells and to instance columns, constants excluded
+    pub fn copies(&self) -> usize {
+        self.copies
+    }
+}
+
+impl<F: FieldExt> Layouter<F> for RecordingLayouter<F> {
+    type Root = Self;
+
+    fn assign_region<A, AR, N, NR>(&mut self, name: N, mut assignment: A) -> Result<AR, Error>
+    where
+        A: FnMut(Region<'_, F>) -> Result<AR, Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        let mut region = RecordingRegion {
+            shape: RegionShape::new(self.region_count.into()),
+            constants: 0,
+            copies: 0,
+        };
+        self.region_count += 1;
+
+        let result = {
+            let region: &mut dyn RegionLayouter<F> = &mut region;
+            assignment(region.into())?
+        };
+
+        // the region starts at the first row where all of its columns are free
+        let shape = region.shape;
+        let start = shape
+            .columns()
+            .iter()
+            .map(|column| self.columns.get(column).copied().unwrap_or(0))
+            .max()
+            .unwrap_or(0);
+        for column in shape.columns() {
+            self.columns.insert(*column, start + shape.row_count());
+        }
+
+        for cost in [self.regions.entry(name().into()).or_default(), self.chips.entry(chip_of::<A>()).or_default()] {
+            cost.count += 1;
+            cost.rows += shape.row_count();
+        }
+        self.constants += region.constants;
+        self.copies += region.copies;
+
+        Ok(result)
+    }
+
+    fn assign_table<A, N, NR>(&mut self, _: N, mut assignment: A) -> Result<(), Error>
+    where
+        A: FnMut(Table<'_, F>) -> Result<(), Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        let mut table = RecordingTable::default();
+        {
+            let table: &mut dyn TableLayouter<F> = &mut table;
+            assignment(table.into())?;
+        }
+
+        self.table_columns.extend(table.columns);
+        self.table_rows = cmp::max(self.table_rows, table.rows);
+        Ok(())
+    }
+
+    fn constrain_instance(&mut self, _: Cell, _: Column<Instance>, _: usize) -> Result<(), Error> {
+        self.copies += 1;
+        Ok(())
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+/// Rows a circuit takes, counted by a dry run of its synthesis
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RowBudget {
+    /// Rows taken by regions, tables and constants
+    pub rows: usize,
+    pub table_rows: usize,
+    /// Rows at the end of every column that are reserved for blinding
+    pub blinding_rows: usize,
+    /// Total rows halo2 requires at least, whatever the circuit
+    pub minimum_rows: usize,
+}
+
+impl RowBudget {
+    pub fn measure<F: FieldExt, C: plonk::Circuit<F>>(circuit: &C) -> Result<Self, Error> {
+        let (meta, layouter) = RecordingLayouter::record(circuit)?;
+        Ok(Self::new(&meta, &layouter))
+    }
+
+    fn new<F: FieldExt>(meta: &ConstraintSystem<F>, layouter: &RecordingLayouter<F>) -> Self {
+        RowBudget {
+            rows: layouter.rows(),
+            table_rows: layouter.table_rows,
+            blinding_rows: meta.blinding_factors() + 1,
+            minimum_rows: meta.minimum_rows(),
+        }
+    }
+
+    /// Smallest k whose usable rows fit the circuit. The constraint degree only sizes the
+    /// extended evaluation domain, so it does not raise k.
+    pub fn min_k(&self) -> u32 {
+        let needed = cmp::max(self.rows + self.blinding_rows, self.minimum_rows);
+        needed.next_power_of_two().trailing_zeros()
+    }
+
+    /// Fails up front where halo2 would report `NotEnoughRowsAvailable`
+    pub fn check(&self, k: u32) -> Result<(), KTooSmall> {
+        if k < self.min_k() {
+            return Err(KTooSmall { k, budget: *self });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KTooSmall {
+    pub k: u32,
+    pub budget: RowBudget,
+}
+
+impl fmt::Display for KTooSmall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let budget = self.budget;
+        write!(
+            f,
+            "k = {} is too small: the circuit takes {} rows ({} for lookup tables) plus {} blinding rows, the smallest k is {}",
+            self.k, budget.rows, budget.table_rows, budget.blinding_rows, budget.min_k()
+        )
+    }
+}
+
+impl std::error::Error for KTooSmall {}
+
+/// Smallest k that `circuit` fits in
+pub fn min_k<F: FieldExt, C: plonk::Circuit<F>>(circuit: &C) -> Result<u32, Error> {
+    Ok(RowBudget::measure(circuit)?.min_k())
+}
+
+/// Number of columns allocated before `next`. This halo2 keeps the column counts of a
+/// `ConstraintSystem` and the indices of columns crate private, the next column allocated is
+/// the only public handle on how many there are.
+fn count<C: PartialEq>(next: C, allocate: impl FnMut() -> C) -> usize {
+    std::iter::repeat_with(allocate).take_while(|column| *column != next).count()
+}
+
+#[derive(Clone, Debug)]
+pub struct CostReport {
+    pub advice_columns: usize,
+    /// Fixed columns besides the table columns
+    pub fixed_columns: usize,
+    pub instance_columns: usize,
+    pub table_columns: usize,
+    pub max_degree: usize,
+    pub lookups: usize,
+    pub rows: usize,
+    pub table_rows: usize,
+    /// Keyed by region name
+    pub regions: BTreeMap<String, RegionCost>,
+    /// Keyed by chip, see `chip_of`, regions the circuit lays out itself are under its own type
+    pub chips: BTreeMap<&'static str, RegionCost>,
+    pub min_k: u32,
+    /// Bytes of a proof of a single circuit at `min_k`
+    pub proof_size: usize,
+}
+
+impl CostReport {
+    /// Measured over `pallas::Base` only, `CircuitCost` sizes the proof for a curve whose scalar
+    /// field is the circuit field, which is `vesta::Point` for pallas
+    pub fn measure<C: plonk::Circuit<pallas::Base>>(circuit: &C) -> Result<Self, Error> {
+        let (meta, layouter) = RecordingLayouter::record(circuit)?;
+
+        // new columns and lookups are numbered after the existing ones
+        let mut fresh = ConstraintSystem::<pallas::Base>::default();
+        let table_columns = layouter.table_columns.len();
+        let fixed_columns = count(meta.clone().fixed_column(), || fresh.fixed_column()) - table_columns;
+        let advice_columns = count(meta.clone().advice_column(), || fresh.advice_column());
+        let instance_columns = count(meta.clone().instance_column(), || fresh.instance_column());
+        let lookups = meta.clone().lookup(|_| vec![]);
+
+        let budget = RowBudget::new(&meta, &layouter);
+        let min_k = budget.min_k();
+        let proof_size = CircuitCost::<vesta::Point, C>::measure(min_k as usize, circuit).proof_size(1).into();
+
+        Ok(CostReport {
+            advice_columns,
+            fixed_columns,
+            instance_columns,
+            table_columns,
+            max_degree: meta.degree(),
+            lookups,
+            rows: budget.rows,
+            table_rows: budget.table_rows,
+            regions: layouter.regions,
+            chips: layouter.chips,
+            min_k,
+            proof_size,
+        })
+    }
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "columns: {} advice, {} fixed, {} instance, {} table",
+            self.advice_columns, self.fixed_columns, self.instance_columns, self.table_columns
+        )?;
+        writeln!(f, "max degree: {}, lookups: {}", self.max_degree, self.lookups)?;
+        writeln!(f, "rows: {} ({} table), min k: {}", self.rows, self.table_rows, self.min_k)?;
+        writeln!(f, "proof size: {} bytes", self.proof_size)?;
+
+        for (chip, cost) in self.chips.iter() {
+            writeln!(f, "  {}: {} regions, {} rows", chip, cost.count, cost.rows)?;
+        }
+        writeln!(f, "regions:")?;
+        for (name, cost) in self.regions.iter() {
+            writeln!(f, "  {}: {} regions, {} rows", name, cost.count, cost.rows)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{dev::MockProver, plonk::Error};
+
+    use super::{min_k, CostReport, RegionCost, RowBudget};
+
+    use crate::circuit::{self, BatchXorCircuit, Circuit};
+
+    #[test]
+    fn xor_circuit_cost() {
+        let report = CostReport::measure(&Circuit::new(1, 2)).unwrap();
+
+        assert_eq!(
+            (report.advice_columns, report.fixed_columns, report.instance_columns, report.table_columns),
+            (5, 1, 1, 3)
+        );
+        assert_eq!(report.lookups, 1);
+        assert_eq!(report.table_rows, 1 << 16);
+        assert_eq!(report.min_k, circuit::K);
+        // the exact size of the proofs written by `prover`
+        assert_eq!(report.proof_size, 3008);
+
+        assert_eq!(report.regions["load bytes"], RegionCost { count: 2, rows: 8 });
+        assert_eq!(report.regions["xor"], RegionCost { count: 1, rows: 4 });
+        assert_eq!(report.regions["pack"], RegionCost { count: 1, rows: 5 });
+
+        // x and y are loaded by the table chip, the circuit xors them in its own region
+        assert_eq!(report.chips["TableChip"], RegionCost { count: 2, rows: 8 });
+        assert_eq!(report.chips["Circuit"], RegionCost { count: 1, rows: 4 });
+        assert_eq!(report.chips["BytesChip"], RegionCost { count: 1, rows: 5 });
+        assert_eq!(report.chips.len(), 3);
+    }
+
+    #[test]
+    fn xor_circuit_min_k() {
+        let (x, y) = (0xdeadbeef, 0x01234567);
+        let circuit = Circuit::new(x, y);
+
+        let k = min_k(&circuit).unwrap();
+        assert_eq!(k, circuit::K);
+
+        let prover = MockProver::run(k, &circuit, vec![Circuit::public_inputs(x ^ y)]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // halo2 itself only reports that rows ran out
+        assert!(matches!(
+            MockProver::run(k - 1, &circuit, vec![Circuit::public_inputs(x ^ y)]),
+            Err(Error::NotEnoughRowsAvailable { .. })
+        ));
+
+        let budget = RowBudget::measure(&circuit).unwrap();
+        assert!(budget.check(k).is_ok());
+
+        let err = budget.check(k - 1).unwrap_err();
+        assert!(err.to_string().ends_with("the smallest k is 17"), "{}", err);
+    }
+
+    #[test]
+    fn batch_circuit_cost() {
+        let report = CostReport::measure(&BatchXorCircuit::with_len(100)).unwrap();
+
+        // all lookup rows in one region, the packing runs alongside in its own columns
+        assert_eq!(report.regions["xor"], RegionCost { count: 1, rows: 400 });
+        assert_eq!(report.regions["pack"], RegionCost { count: 100, rows: 500 });
+        assert_eq!(report.chips["BatchXorCircuit"], RegionCost { count: 1, rows: 400 });
+        assert_eq!(report.chips["BytesChip"], RegionCost { count: 100, rows: 500 });
+        assert_eq!(report.min_k, circuit::K);
+
+        // five packing rows per pair outgrow the xor table
+        assert_eq!(min_k(&BatchXorCircuit::with_len(30000)).unwrap(), circuit::K + 1);
+    }
+}
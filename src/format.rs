@@ -0,0 +1,255 @@
+use halo2::{
+    pasta::{group::ff::PrimeField, EqAffine, Fp},
+    plonk::{self, keygen_vk, VerifyingKey},
+    poly::commitment::Params,
+    transcript::{Blake2bWrite, Challenge255, EncodedChallenge, Transcript},
+};
+use std::{fmt, io};
+
+use crate::circuit;
+use crate::gadget::xor::XOR_BITS;
+
+pub const MAGIC: [u8; 4] = *b"XORS";
+pub const VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Params = 0,
+    VerifyingKey = 1,
+    Proof = 2,
+}
+
+/// Written in front of every file, loading checks it against the expected header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub kind: Kind,
+    pub circuit_id: u32,
+    pub k: u32,
+    pub chunk_width: u32,
+}
+
+#[derive(Debug)]
+pub enum FormatError {
+    Io(io::Error),
+    Magic([u8; 4]),
+    Version(u32),
+    Kind(u32),
+    Mismatch {
+        field: &'static str,
+        expected: u32,
+        found: u32,
+    },
+    VerifyingKey,
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::Io(err) => write!(f, "{}", err),
+            FormatError::Magic(magic) => write!(f, "not a xors file, magic is {:?}", magic),
+            FormatError::Version(version) => write!(f, "unsupported format version {}, expected {}", version, VERSION),
+            FormatError::Kind(kind) => write!(f, "unknown file kind {}", kind),
+            FormatError::Mismatch { field, expected, found } => {
+                write!(f, "{} is {} but the circuit is configured for {}", field, found, expected)
+            }
+            FormatError::VerifyingKey => write!(f, "verifying key does not match the circuit"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl From<io::Error> for FormatError {
+    fn from(err: io::Error) -> Self {
+        FormatError::Io(err)
+    }
+}
+
+impl Header {
+    /// Header of the xor circuit in `circuit`
+    pub fn xor(kind: Kind, k: u32) -> Self {
+        Header {
+            kind,
+            circuit_id: circuit::ID,
+            k,
+            chunk_width: XOR_BITS as u32,
+        }
+    }
+
+    pub fn write<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&MAGIC)?;
+        for field in [VERSION, self.kind as u32, self.circuit_id, self.k, self.chunk_width] {
+            writer.write_all(&field.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read<R: io::Read>(reader: &mut R) -> Result<Self, FormatError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(FormatError::Magic(magic));
+        }
+
+        let mut read_u32 = || -> io::Result<u32> {
+            let mut bytes = [0u8; 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes))
+        };
+
+        let version = read_u32()?;
+        if version != VERSION {
+            return Err(FormatError::Version(version));
+        }
+
+        let kind = match read_u32()? {
+            0 => Kind::Params,
+            1 => Kind::VerifyingKey,
+            2 => Kind::Proof,
+            kind => return Err(FormatError::Kind(kind)),
+        };
+
+        Ok(Header {
+            kind,
+            circuit_id: read_u32()?,
+            k: read_u32()?,
+            chunk_width: read_u32()?,
+        })
+    }
+
+    /// Reads a header and fails on the first field that differs from `self`
+    pub fn expect<R: io::Read>(&self, reader: &mut R) -> Result<(), FormatError> {
+        let found = Header::read(reader)?;
+
+        for (field, expected, found) in [
+            ("file kind", self.kind as u32, found.kind as u32),
+            ("circuit id", self.circuit_id, found.circuit_id),
+            ("k", self.k, found.k),
+            ("chunk width", self.chunk_width, found.chunk_width),
+        ] {
+            if expected != found {
+                return Err(FormatError::Mismatch { field, expected, found });
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn write_params<W: io::Write>(writer: &mut W, header: Header, params: &Params<EqAffine>) -> io::Result<()> {
+    header.write(writer)?;
+    params.write(writer)
+}
+
+pub fn read_params<R: io::Read>(reader: &mut R, header: Header) -> Result<Params<EqAffine>, FormatError> {
+    header.expect(reader)?;
+    let params = Params::read(reader)?;
+
+    let n = params.get_g().len() as u32;
+    if n != 1 << header.k {
+        return Err(FormatError::Mismatch { field: "params size", expected: 1 << header.k, found: n });
+    }
+    Ok(params)
+}
+
+/// Challenge squeezed from a transcript the verifying key was hashed into. `hash_into` is how
+/// halo2 binds every proof to its key, so two keys with the same fingerprint verify the same proofs.
+///
+/// This version of halo2 cannot write the key itself: its fixed and permutation commitments are
+/// crate private and a key can only be built by `keygen_vk`, so the vk file holds this
+/// fingerprint and `read_vk` regenerates the key.
+pub fn fingerprint(vk: &VerifyingKey<EqAffine>) -> [u8; 32] {
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    vk.hash_into(&mut transcript)
+        .expect("hashing into an in-memory transcript cannot fail");
+    transcript.squeeze_challenge().get_scalar().to_repr()
+}
+
+pub fn write_vk<W: io::Write>(writer: &mut W, header: Header, vk: &VerifyingKey<EqAffine>) -> io::Result<()> {
+    header.write(writer)?;
+    writer.write_all(&fingerprint(vk))
+}
+
+/// Regenerates the verifying key of `circuit` and checks it against the stored fingerprint
+pub fn read_vk<R: io::Read, C: plonk::Circuit<Fp>>(
+    reader: &mut R,
+    header: Header,
+    params: &Params<EqAffine>,
+    circuit: &C,
+) -> Result<VerifyingKey<EqAffine>, FormatError> {
+    header.expect(reader)?;
+
+    let mut expected = [0u8; 32];
+    reader.read_exact(&mut expected)?;
+
+    let vk = keygen_vk(params, circuit).map_err(|_| FormatError::VerifyingKey)?;
+    if fingerprint(&vk) != expected {
+        return Err(FormatError::VerifyingKey);
+    }
+    Ok(vk)
+}
+
+pub fn write_proof<W: io::Write>(writer: &mut W, header: Header, proof: &[u8]) -> io::Result<()> {
+    header.write(writer)?;
+    writer.write_all(proof)
+}
+
+pub fn read_proof<R: io::Read>(reader: &mut R, header: Header) -> Result<Vec<u8>, FormatError> {
+    header.expect(reader)?;
+
+    let mut proof = vec![];
+    reader.read_to_end(&mut proof)?;
+    Ok(proof)
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{pasta::EqAffine, plonk::keygen_vk, poly::commitment::Params};
+
+    use super::{read_params, read_proof, read_vk, write_params, write_proof, write_vk, FormatError, Header, Kind};
+
+    use crate::circuit::{self, Circuit};
+
+    #[test]
+    fn header_roundtrip() {
+        let header = Header::xor(Kind::Proof, circuit::K);
+
+        let mut bytes = vec![];
+        write_proof(&mut bytes, header, &[1, 2, 3]).unwrap();
+        assert_eq!(read_proof(&mut bytes.as_slice(), header).unwrap(), vec![1, 2, 3]);
+
+        // a proof is not a params file
+        let err = read_params(&mut bytes.as_slice(), Header::xor(Kind::Params, circuit::K)).unwrap_err();
+        assert!(matches!(err, FormatError::Mismatch { field: "file kind", .. }));
+
+        let err = read_proof(&mut bytes.as_slice(), Header::xor(Kind::Proof, circuit::K + 1)).unwrap_err();
+        assert!(matches!(err, FormatError::Mismatch { field: "k", .. }));
+
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 2;
+        assert!(matches!(read_proof(&mut wrong_version.as_slice(), header), Err(FormatError::Version(2))));
+
+        let mut wrong_magic = bytes;
+        wrong_magic[0] = b'Y';
+        assert!(matches!(read_proof(&mut wrong_magic.as_slice(), header), Err(FormatError::Magic(_))));
+    }
+
+    #[test]
+    fn params_and_vk_roundtrip() {
+        let k = circuit::K;
+        let params = Params::<EqAffine>::new(k);
+        let vk = keygen_vk(&params, &Circuit::default()).unwrap();
+
+        let mut params_bytes = vec![];
+        write_params(&mut params_bytes, Header::xor(Kind::Params, k), &params).unwrap();
+        let mut vk_bytes = vec![];
+        write_vk(&mut vk_bytes, Header::xor(Kind::VerifyingKey, k), &vk).unwrap();
+
+        let params = read_params(&mut params_bytes.as_slice(), Header::xor(Kind::Params, k)).unwrap();
+        read_vk(&mut vk_bytes.as_slice(), Header::xor(Kind::VerifyingKey, k), &params, &Circuit::default()).unwrap();
+
+        let mut wrong_vk = vk_bytes;
+        *wrong_vk.last_mut().unwrap() ^= 1;
+        let err = read_vk(&mut wrong_vk.as_slice(), Header::xor(Kind::VerifyingKey, k), &params, &Circuit::default()).unwrap_err();
+        assert!(matches!(err, FormatError::VerifyingKey));
+    }
+}
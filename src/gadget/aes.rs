@@ -0,0 +1,573 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter, Region},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector, TableColumn},
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::lookup::LookupTable;
+use crate::gadget::xor::{TableChip, TableConfig};
+use crate::word::{AssignedChunk, Chunk};
+
+pub const BLOCK_BYTES: usize = 16;
+
+/// A state or key as range checked bytes, in column-major order
+pub type Block<F> = [AssignedChunk<F>; BLOCK_BYTES];
+
+const ROUNDS: usize = 10;
+
+const ROUND_CONSTANTS: [u8; ROUNDS] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// Multiplication by x in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1
+pub const fn xtime(x: u8) -> u8 {
+    (x << 1) ^ if x & 0x80 != 0 { 0x1b } else { 0 }
+}
+
+const fn gf_mul(mut x: u8, mut y: u8) -> u8 {
+    let mut product = 0;
+    while y != 0 {
+        if y & 1 != 0 {
+            product ^= x;
+        }
+        x = xtime(x);
+        y >>= 1;
+    }
+    product
+}
+
+const fn gf_pow(x: u8, mut n: u8) -> u8 {
+    let (mut base, mut power) = (x, 1);
+    while n != 0 {
+        if n & 1 != 0 {
+            power = gf_mul(power, base);
+        }
+        base = gf_mul(base, base);
+        n >>= 1;
+    }
+    power
+}
+
+const fn sbox() -> [u8; 256] {
+    let mut sbox = [0; 256];
+    let mut x = 0;
+    while x < 256 {
+        // x^254 is the inverse of x, and maps 0 to 0
+        let inverse = gf_pow(x as u8, 254);
+        sbox[x] = inverse
+            ^ inverse.rotate_left(1)
+            ^ inverse.rotate_left(2)
+            ^ inverse.rotate_left(3)
+            ^ inverse.rotate_left(4)
+            ^ 0x63;
+        x += 1;
+    }
+    sbox
+}
+
+/// The AES S-box: multiplicative inverse in GF(2^8) followed by the affine transform
+pub const SBOX: [u8; 256] = sbox();
+
+fn sbox_entry(row: usize) -> [u64; 3] {
+    [row as u64, SBOX[row] as u64, xtime(SBOX[row]) as u64]
+}
+
+const SBOX_TABLE: LookupTable = LookupTable {
+    name: "sbox table",
+    len: 1 << 8,
+    row: sbox_entry,
+};
+
+/// Lookup rows of (x, sbox(x), xtime(sbox(x))), so a single lookup serves both SubBytes
+/// and the doubling in the following MixColumns
+#[derive(Clone, Debug)]
+pub struct SboxTableConfig {
+    pub q_lookup: Selector,
+    pub input: [Column<Advice>; 3],
+    pub table: [TableColumn; 3],
+}
+
+#[derive(Clone, Debug)]
+pub struct SboxTableChip<F: FieldExt> {
+    config: SboxTableConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for SboxTableChip<F> {
+    type Config = SboxTableConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> SboxTableChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        input: [Column<Advice>; 3],
+    ) -> <Self as Chip<F>>::Config {
+        // sbox(0) is not 0, so rows without the selector look up the first table row instead
+        let q_lookup = meta.complex_selector();
+        let table = SBOX_TABLE.configure(meta, input, Some(q_lookup));
+
+        SboxTableConfig {
+            q_lookup,
+            input,
+            table,
+        }
+    }
+
+    pub fn construct(config: SboxTableConfig) -> Self {
+        SboxTableChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: SboxTableConfig,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<<Self as Chip<F>>::Loaded, Error> {
+        SBOX_TABLE.load(layouter, config.table)
+    }
+
+    /// Copies `x` into the lookup row and witnesses `(sbox(x), xtime(sbox(x)))`
+    pub fn sbox_row(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        x: &AssignedChunk<F>,
+    ) -> Result<(AssignedChunk<F>, AssignedChunk<F>), Error> {
+        let config = self.config();
+
+        config.q_lookup.enable(region, row)?;
+        x.copy(|| format!("x: {}", row), region, config.input[0], row)?;
+
+        let s = x.value_word().map(|x| Chunk::new(SBOX[*x as usize] as u64));
+        let t = s.map(|s| Chunk::new(xtime(*s as u8) as u64));
+
+        let s = region.assign_advice(
+            || format!("sbox: {}", row),
+            config.input[1],
+            row,
+            || s.ok_or(Error::Synthesis)
+        )?;
+
+        let t = region.assign_advice(
+            || format!("xtime: {}", row),
+            config.input[2],
+            row,
+            || t.ok_or(Error::Synthesis)
+        )?;
+
+        Ok((AssignedChunk::new(s), AssignedChunk::new(t)))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Aes128Config {
+    pub sbox_table_config: SboxTableConfig,
+    pub table_config: TableConfig,
+}
+
+/// AES-128 encryption of a single block. The state is kept as 16 bytes in column-major
+/// order, so ShiftRows is only a permutation of cells.
+#[derive(Clone, Debug)]
+pub struct Aes128Chip<F> {
+    config: Aes128Config,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for Aes128Chip<F> {
+    type Config = Aes128Config;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> Aes128Chip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        constants: Column<Fixed>,
+        sbox_table_config: SboxTableConfig,
+        table_config: TableConfig,
+    ) -> Aes128Config {
+        meta.enable_constant(constants);
+
+        Aes128Config {
+            sbox_table_config,
+            table_config,
+        }
+    }
+
+    pub fn construct(config: Aes128Config) -> Self {
+        Aes128Chip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: Aes128Config,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.table_config.clone(), layouter)?;
+        SboxTableChip::load(config.sbox_table_config, layouter)
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.table_config.clone())
+    }
+
+    fn sbox_table_chip(&self) -> SboxTableChip<F> {
+        SboxTableChip::construct(self.config.sbox_table_config.clone())
+    }
+
+    /// Witnesses the bytes of a key or block, range checked through the xor table
+    pub fn load_block(
+        &self,
+        mut layouter: impl Layouter<F>,
+        block: [Option<u8>; BLOCK_BYTES],
+    ) -> Result<Block<F>, Error> {
+        let table_chip = self.table_chip();
+
+        let bytes = layouter.assign_region(
+            || "load block",
+            |mut region| {
+                block
+                    .iter()
+                    .enumerate()
+                    .map(|(row, byte)| table_chip.load_chunk(&mut region, row, byte.map(|byte| Chunk::new(byte as u64))))
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?;
+
+        Ok(bytes.try_into().unwrap())
+    }
+
+    /// Encrypts a range checked `block` under a range checked `key`
+    pub fn encrypt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: &Block<F>,
+        block: &Block<F>,
+    ) -> Result<Block<F>, Error> {
+        let round_keys = self.expand_key(layouter.namespace(|| "key expansion"), key)?;
+
+        let mut state = self.add_round_key(layouter.namespace(|| "initial round key"), block, &round_keys[0])?;
+
+        for (round, round_key) in round_keys.iter().enumerate().skip(1) {
+            let (substituted, doubled) = self.sub_bytes(layouter.namespace(|| format!("sub bytes {}", round)), &state)?;
+
+            let shifted = shift_rows(&substituted);
+            state = if round < ROUNDS {
+                let doubled = shift_rows(&doubled);
+                self.mix_columns(layouter.namespace(|| format!("mix columns {}", round)), &shifted, &doubled)?
+            } else {
+                shifted
+            };
+
+            state = self.add_round_key(layouter.namespace(|| format!("round key {}", round)), &state, round_key)?;
+        }
+
+        Ok(state)
+    }
+
+    /// The 11 round keys of the AES-128 key schedule
+    pub fn expand_key(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: &Block<F>,
+    ) -> Result<Vec<Block<F>>, Error> {
+        let table_chip = self.table_chip();
+        let sbox_table_chip = self.sbox_table_chip();
+
+        let mut round_keys = vec![key.clone()];
+
+        for (round, rcon) in ROUND_CONSTANTS.iter().enumerate() {
+            let previous = round_keys.last().unwrap().clone();
+
+            // SubWord(RotWord(w)) of the last word of the previous round key
+            let substituted = layouter.assign_region(
+                || format!("sub word {}", round),
+                |mut region| {
+                    (0..4)
+                        .map(|i| {
+                            let (s, _) = sbox_table_chip.sbox_row(&mut region, i, &previous[12 + (i + 1) % 4])?;
+                            Ok(s)
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                },
+            )?;
+
+            let round_key = layouter.assign_region(
+                || format!("round key {}", round + 1),
+                |mut region| {
+                    let mut row = 0;
+                    let mut temp = substituted.clone();
+                    temp[0] = table_chip.xor_constant_row(&mut region, row, &temp[0], Chunk::new(*rcon as u64))?;
+                    row += 1;
+
+                    let mut round_key = Vec::with_capacity(BLOCK_BYTES);
+                    for word in 0..4 {
+                        for i in 0..4 {
+                            temp[i] = table_chip.xor_row(&mut region, row, &previous[4 * word + i], &temp[i])?;
+                            row += 1;
+                        }
+                        round_key.extend(temp.iter().cloned());
+                    }
+
+                    Ok(round_key)
+                },
+            )?;
+
+            round_keys.push(round_key.try_into().unwrap());
+        }
+
+        Ok(round_keys)
+    }
+
+    /// SubBytes, also returning xtime of every substituted byte
+    fn sub_bytes(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &Block<F>,
+    ) -> Result<(Block<F>, Block<F>), Error> {
+        let sbox_table_chip = self.sbox_table_chip();
+
+        let (substituted, doubled): (Vec<_>, Vec<_>) = layouter.assign_region(
+            || "sub bytes",
+            |mut region| {
+                state
+                    .iter()
+                    .enumerate()
+                    .map(|(row, byte)| sbox_table_chip.sbox_row(&mut region, row, byte))
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?.into_iter().unzip();
+
+        Ok((substituted.try_into().unwrap(), doubled.try_into().unwrap()))
+    }
+
+    /// MixColumns of the state `a` given `b = xtime(a)`:
+    /// out[i] = (a[i] ^ b[i]) ^ b[i + 1] ^ (a[0] ^ a[1] ^ a[2] ^ a[3])
+    fn mix_columns(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &Block<F>,
+        b: &Block<F>,
+    ) -> Result<Block<F>, Error> {
+        let table_chip = self.table_chip();
+
+        let mixed = layouter.assign_region(
+            || "mix columns",
+            |mut region| {
+                let mut row = 0;
+                let mut xor = |x: &AssignedChunk<F>, y: &AssignedChunk<F>| {
+                    let z = table_chip.xor_row(&mut region, row, x, y);
+                    row += 1;
+                    z
+                };
+
+                let mut mixed = Vec::with_capacity(BLOCK_BYTES);
+                for column in 0..4 {
+                    let a = &a[4 * column..4 * column + 4];
+                    let b = &b[4 * column..4 * column + 4];
+
+                    let t = xor(&a[0], &a[1])?;
+                    let t = xor(&t, &a[2])?;
+                    let t = xor(&t, &a[3])?;
+
+                    for i in 0..4 {
+                        let out = xor(&a[i], &b[i])?;
+                        let out = xor(&out, &b[(i + 1) % 4])?;
+                        mixed.push(xor(&out, &t)?);
+                    }
+                }
+
+                Ok(mixed)
+            },
+        )?;
+
+        Ok(mixed.try_into().unwrap())
+    }
+
+    fn add_round_key(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &Block<F>,
+        round_key: &Block<F>,
+    ) -> Result<Block<F>, Error> {
+        let table_chip = self.table_chip();
+
+        let state = layouter.assign_region(
+            || "add round key",
+            |mut region| {
+                state
+                    .iter()
+                    .zip(round_key.iter())
+                    .enumerate()
+                    .map(|(row, (x, k))| table_chip.xor_row(&mut region, row, x, k))
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?;
+
+        Ok(state.try_into().unwrap())
+    }
+}
+
+/// Row r of the column-major state is rotated left by r
+fn shift_rows<F: FieldExt>(state: &Block<F>) -> Block<F> {
+    let shifted: Vec<AssignedChunk<F>> = (0..BLOCK_BYTES)
+        .map(|i| {
+            let (row, column) = (i % 4, i / 4);
+            state[row + 4 * ((column + row) % 4)].clone()
+        })
+        .collect();
+
+    shifted.try_into().unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use std::convert::TryInto;
+
+    use super::{Aes128Chip, Aes128Config, SboxTableChip, BLOCK_BYTES, SBOX};
+
+    use crate::gadget::xor::TableChip;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        aes_config: Aes128Config,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        key: [Option<u8>; BLOCK_BYTES],
+        plaintext: [Option<u8>; BLOCK_BYTES],
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[0], advice[1], advice[2]);
+            let sbox_table_config = SboxTableChip::configure(meta, advice[3..6].try_into().unwrap());
+            let aes_config = Aes128Chip::configure(meta, constants, sbox_table_config, table_config);
+
+            Config {
+                instance,
+                aes_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            Aes128Chip::<F>::load(config.aes_config.clone(), &mut layouter)?;
+            let chip = Aes128Chip::<F>::construct(config.aes_config.clone());
+
+            let key = chip.load_block(layouter.namespace(|| "key"), self.key)?;
+            let plaintext = chip.load_block(layouter.namespace(|| "plaintext"), self.plaintext)?;
+
+            let ciphertext = chip.encrypt(layouter.namespace(|| "aes128"), &key, &plaintext)?;
+
+            for (row, byte) in ciphertext.iter().enumerate() {
+                layouter.constrain_instance(byte.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn hex(hex: &str) -> [u8; BLOCK_BYTES] {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        bytes.try_into().unwrap()
+    }
+
+    fn circuit(key: &str, plaintext: &str) -> Circuit {
+        Circuit {
+            key: hex(key).map(Some),
+            plaintext: hex(plaintext).map(Some),
+        }
+    }
+
+    fn ciphertext<F: FieldExt>(ciphertext: &str) -> Vec<F> {
+        hex(ciphertext).iter().map(|byte| F::from(*byte as u64)).collect()
+    }
+
+    #[test]
+    fn native_sbox() {
+        assert_eq!(SBOX[0x00], 0x63);
+        assert_eq!(SBOX[0x01], 0x7c);
+        assert_eq!(SBOX[0x53], 0xed);
+        assert_eq!(SBOX[0xff], 0x16);
+    }
+
+    fn aes128_fips197<F: FieldExt>() {
+        let k = 17;
+
+        // FIPS-197 appendix B and appendix C.1
+        let vectors = [
+            ("2b7e151628aed2a6abf7158809cf4f3c", "3243f6a8885a308d313198a2e0370734", "3925841d02dc09fbdc118597196a0b32"),
+            ("000102030405060708090a0b0c0d0e0f", "00112233445566778899aabbccddeeff", "69c4e0d86a7b0430d8cdb78070b4c55a"),
+        ];
+
+        for (key, plaintext, expected) in vectors {
+            let prover = MockProver::<F>::run(k, &circuit(key, plaintext), vec![ciphertext(expected)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn aes128_wrong_key<F: FieldExt>() {
+        let k = 17;
+
+        let circuit = circuit("2b7e151628aed2a6abf7158809cf4f3d", "3243f6a8885a308d313198a2e0370734");
+        let prover = MockProver::<F>::run(k, &circuit, vec![ciphertext("3925841d02dc09fbdc118597196a0b32")]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(aes128_fips197, aes128_wrong_key);
+}
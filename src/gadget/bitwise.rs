@@ -0,0 +1,1033 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector},
+    poly::Rotation,
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::{
+    decompose::{DecomposeChip, DecomposeConfig, DecomposeInstruction},
+    xor::{TableChip, TableConfig},
+};
+use crate::gates::Gate;
+use crate::word::{AssignedChunk, AssignedWord, Chunk, Word};
+
+/// Operations on `BITS` wide words built from the byte decompose gate and the
+/// byte xor table.
+///
+/// Inputs are expected to be range checked words, i.e. either loaded through
+/// `load_word` or produced by another instruction of this chip.
+pub trait BitwiseInstructions<F: FieldExt, const BITS: usize> {
+    fn load_word(
+        &self,
+        layouter: impl Layouter<F>,
+        value: Option<Word<BITS>>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    fn constant(
+        &self,
+        layouter: impl Layouter<F>,
+        value: Word<BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    fn xor(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    fn and(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    fn or(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    fn not(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    /// Addition mod 2^BITS
+    fn add(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    /// Full product as `(lo, hi)` with `a * b = lo + 2^BITS * hi`
+    fn mul(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<(AssignedWord<F, BITS>, AssignedWord<F, BITS>), Error>;
+
+    fn rotl(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        n: u32,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    fn rotr(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        n: u32,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        self.rotl(layouter, a, ((BITS - n as usize % BITS) % BITS) as u32)
+    }
+
+    /// Logical shift right, `a` itself for `n = 0` and zero for `n >= BITS`
+    fn shr(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        n: u32,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    /// Multiplication mod 2^BITS by a constant
+    fn mul_constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        constant: Word<BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let constant = self.constant(layouter.namespace(|| "constant"), constant)?;
+        let (lo, _) = self.mul(layouter.namespace(|| "mul"), a, &constant)?;
+        Ok(lo)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BitwiseConfig<const BITS: usize, const LIMBS: usize> {
+    pub q_and: Selector,
+    pub q_or: Selector,
+    pub q_not: Selector,
+    pub q_add: Selector,
+    pub q_mul: Selector,
+    pub q_rotl: Selector,
+    pub q_shr: Selector,
+    pub shift: Column<Fixed>,
+    pub advice: [Column<Advice>; 3],
+    pub decompose_config: DecomposeConfig<BITS, LIMBS>,
+    pub table_config: TableConfig,
+}
+
+pub type Bitwise32Config = BitwiseConfig<32, 4>;
+pub type Bitwise64Config = BitwiseConfig<64, 8>;
+
+#[derive(Clone, Debug)]
+pub struct BitwiseChip<F, const BITS: usize, const LIMBS: usize> {
+    config: BitwiseConfig<BITS, LIMBS>,
+    _marker: PhantomData<F>,
+}
+
+pub type Bitwise32Chip<F> = BitwiseChip<F, 32, 4>;
+pub type Bitwise64Chip<F> = BitwiseChip<F, 64, 8>;
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> Chip<F> for BitwiseChip<F, BITS, LIMBS> {
+    type Config = BitwiseConfig<BITS, LIMBS>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> BitwiseChip<F, BITS, LIMBS> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        constants: Column<Fixed>,
+        decompose_config: DecomposeConfig<BITS, LIMBS>,
+        table_config: TableConfig,
+    ) -> BitwiseConfig<BITS, LIMBS> {
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+        meta.enable_constant(constants);
+
+        let config = BitwiseConfig {
+            q_and: meta.selector(),
+            q_or: meta.selector(),
+            q_not: meta.selector(),
+            q_add: meta.selector(),
+            q_mul: meta.selector(),
+            q_rotl: meta.selector(),
+            q_shr: meta.selector(),
+            shift: meta.fixed_column(),
+            advice,
+            decompose_config,
+            table_config,
+        };
+
+        meta.create_gate("and", |meta| {
+            let q_and = meta.query_selector(config.q_and);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let b = meta.query_advice(config.advice[1], Rotation::cur());
+            let xor = meta.query_advice(config.advice[2], Rotation::cur());
+            let out = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::and(q_and, a, b, xor, out)
+        });
+
+        meta.create_gate("or", |meta| {
+            let q_or = meta.query_selector(config.q_or);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let b = meta.query_advice(config.advice[1], Rotation::cur());
+            let xor = meta.query_advice(config.advice[2], Rotation::cur());
+            let out = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::or(q_or, a, b, xor, out)
+        });
+
+        meta.create_gate("not", |meta| {
+            let q_not = meta.query_selector(config.q_not);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let out = meta.query_advice(config.advice[1], Rotation::cur());
+
+            Gate::not(q_not, a, out, BITS as u32)
+        });
+
+        meta.create_gate("add", |meta| {
+            let q_add = meta.query_selector(config.q_add);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let b = meta.query_advice(config.advice[1], Rotation::cur());
+            let out = meta.query_advice(config.advice[2], Rotation::cur());
+            let carry = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::add_with_carry(q_add, a, b, out, carry, BITS as u32)
+        });
+
+        meta.create_gate("mul", |meta| {
+            let q_mul = meta.query_selector(config.q_mul);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let b = meta.query_advice(config.advice[1], Rotation::cur());
+            let lo = meta.query_advice(config.advice[2], Rotation::cur());
+            let hi = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::mul_wide(q_mul, a, b, lo, hi, BITS as u32)
+        });
+
+        meta.create_gate("rotl", |meta| {
+            let q_rotl = meta.query_selector(config.q_rotl);
+            let shift = meta.query_fixed(config.shift, Rotation::cur());
+
+            let w = meta.query_advice(config.advice[0], Rotation::cur());
+            let hi = meta.query_advice(config.advice[1], Rotation::cur());
+            let lo = meta.query_advice(config.advice[2], Rotation::cur());
+            let out = meta.query_advice(config.advice[0], Rotation::next());
+            let hi_shifted = meta.query_advice(config.advice[1], Rotation::next());
+
+            Gate::rotate_left(q_rotl, shift, w, hi, lo, out, hi_shifted, BITS as u32)
+        });
+
+        meta.create_gate("shr", |meta| {
+            let q_shr = meta.query_selector(config.q_shr);
+            let shift = meta.query_fixed(config.shift, Rotation::cur());
+
+            let w = meta.query_advice(config.advice[0], Rotation::cur());
+            let hi = meta.query_advice(config.advice[1], Rotation::cur());
+            let lo = meta.query_advice(config.advice[2], Rotation::cur());
+            let lo_shifted = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::shift_right(q_shr, shift, w, hi, lo, lo_shifted, BITS as u32)
+        });
+
+        config
+    }
+
+    pub fn construct(config: BitwiseConfig<BITS, LIMBS>) -> Self {
+        BitwiseChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    fn decompose_chip(&self) -> DecomposeChip<F, BITS, LIMBS> {
+        DecomposeChip::construct(self.config.decompose_config.clone())
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.table_config.clone())
+    }
+
+    /// Range checks `value` to `BITS` bits by decomposing it into bytes
+    fn range_check(
+        &self,
+        layouter: impl Layouter<F>,
+        value: &AssignedWord<F, BITS>,
+    ) -> Result<(), Error> {
+        self.decompose_chip().decompose(layouter, value.clone())?;
+        Ok(())
+    }
+
+    /// Little-endian word of up to `LIMBS` range checked bytes, zero extended
+    pub fn compose_le_padded(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[AssignedChunk<F>],
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+        assert!(bytes.len() <= LIMBS);
+
+        let mut chunks = bytes.to_vec();
+        if bytes.len() < LIMBS {
+            let zeros = layouter.assign_region(
+                || "zero chunks",
+                |mut region| {
+                    (bytes.len()..LIMBS)
+                        .map(|i| {
+                            let assigned = region.assign_advice_from_constant(
+                                || format!("zero {}", i),
+                                config.advice[i % 3],
+                                i / 3,
+                                Chunk::new(0),
+                            )?;
+                            Ok(AssignedChunk::new(assigned))
+                        })
+                        .collect::<Result<Vec<_>, Error>>()
+                },
+            )?;
+            chunks.extend(zeros);
+        }
+
+        self.decompose_chip().compose(layouter.namespace(|| "compose"), chunks.try_into().unwrap())
+    }
+
+    /// Shared layout of `and` and `or`: a, b, a ^ b on the first row, the result below.
+    fn combine(
+        &self,
+        mut layouter: impl Layouter<F>,
+        selector: Selector,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+        out: Option<Word<BITS>>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+        let xor = self.xor(layouter.namespace(|| "xor"), a, b)?;
+
+        layouter.assign_region(
+            || "combine",
+            |mut region| {
+                selector.enable(&mut region, 0)?;
+
+                a.copy(|| "copy a", &mut region, config.advice[0], 0)?;
+                b.copy(|| "copy b", &mut region, config.advice[1], 0)?;
+                xor.copy(|| "copy xor", &mut region, config.advice[2], 0)?;
+
+                let assigned = region.assign_advice(
+                    || "out",
+                    config.advice[0],
+                    1,
+                    || out.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedWord::<F, BITS>::new(assigned))
+            },
+        )
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> BitwiseInstructions<F, BITS> for BitwiseChip<F, BITS, LIMBS> {
+    fn load_word(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Option<Word<BITS>>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+
+        let word = AssignedWord::<F, BITS>::assign_word(layouter.namespace(|| "witness word"), config.advice[0], value)?;
+        self.range_check(layouter.namespace(|| "range check"), &word)?;
+
+        Ok(word)
+    }
+
+    fn constant(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: Word<BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "constant",
+            |mut region| {
+                let assigned = region.assign_advice_from_constant(
+                    || "constant",
+                    config.advice[0],
+                    0,
+                    value,
+                )?;
+
+                Ok(AssignedWord::<F, BITS>::new(assigned))
+            },
+        )
+    }
+
+    fn xor(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let decompose_chip = self.decompose_chip();
+        let table_chip = self.table_chip();
+
+        let a = decompose_chip.decompose(layouter.namespace(|| "decompose a"), a.clone())?;
+        let b = decompose_chip.decompose(layouter.namespace(|| "decompose b"), b.clone())?;
+
+        let z = layouter.assign_region(
+            || "xor",
+            |mut region| {
+                let z = a
+                    .iter()
+                    .zip(b.iter())
+                    .enumerate()
+                    .map(|(row, (a, b))| table_chip.xor_row(&mut region, row, a, b))
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                Ok(z.try_into().unwrap())
+            },
+        )?;
+
+        decompose_chip.compose(layouter.namespace(|| "compose"), z)
+    }
+
+    fn and(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let out = a.value_word().zip(b.value_word())
+            .map(|(a, b)| a & b);
+
+        self.combine(layouter, self.config().q_and, a, b, out)
+    }
+
+    fn or(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let out = a.value_word().zip(b.value_word())
+            .map(|(a, b)| a | b);
+
+        self.combine(layouter, self.config().q_or, a, b, out)
+    }
+
+    fn not(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+        let out = a.value_word().map(|a| !a);
+
+        layouter.assign_region(
+            || "not",
+            |mut region| {
+                config.q_not.enable(&mut region, 0)?;
+
+                a.copy(|| "copy a", &mut region, config.advice[0], 0)?;
+
+                let assigned = region.assign_advice(
+                    || "out",
+                    config.advice[1],
+                    0,
+                    || out.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedWord::<F, BITS>::new(assigned))
+            },
+        )
+    }
+
+    fn add(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+
+        let sum = a.value_word().zip(b.value_word())
+            .map(|(a, b)| *a as u128 + *b as u128);
+        let out = sum.map(|sum| Word::<BITS>::new((sum & Word::<BITS>::MASK as u128) as u64));
+        let carry = sum.map(|sum| Word::<BITS>::new((sum >> BITS) as u64));
+
+        let out = layouter.assign_region(
+            || "add",
+            |mut region| {
+                config.q_add.enable(&mut region, 0)?;
+
+                a.copy(|| "copy a", &mut region, config.advice[0], 0)?;
+                b.copy(|| "copy b", &mut region, config.advice[1], 0)?;
+
+                let out = region.assign_advice(
+                    || "out",
+                    config.advice[2],
+                    0,
+                    || out.ok_or(Error::Synthesis),
+                )?;
+
+                region.assign_advice(
+                    || "carry",
+                    config.advice[0],
+                    1,
+                    || carry.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedWord::<F, BITS>::new(out))
+            },
+        )?;
+
+        self.range_check(layouter.namespace(|| "range check out"), &out)?;
+
+        Ok(out)
+    }
+
+    fn mul(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<(AssignedWord<F, BITS>, AssignedWord<F, BITS>), Error> {
+        let config = self.config();
+
+        let product = a.value_word().zip(b.value_word())
+            .map(|(a, b)| *a as u128 * *b as u128);
+        let lo = product.map(|product| Word::<BITS>::new((product & Word::<BITS>::MASK as u128) as u64));
+        let hi = product.map(|product| Word::<BITS>::new((product >> BITS) as u64));
+
+        let (lo, hi) = layouter.assign_region(
+            || "mul",
+            |mut region| {
+                config.q_mul.enable(&mut region, 0)?;
+
+                a.copy(|| "copy a", &mut region, config.advice[0], 0)?;
+                b.copy(|| "copy b", &mut region, config.advice[1], 0)?;
+
+                let lo = region.assign_advice(
+                    || "lo",
+                    config.advice[2],
+                    0,
+                    || lo.ok_or(Error::Synthesis),
+                )?;
+
+                let hi = region.assign_advice(
+                    || "hi",
+                    config.advice[0],
+                    1,
+                    || hi.ok_or(Error::Synthesis),
+                )?;
+
+                Ok((AssignedWord::<F, BITS>::new(lo), AssignedWord::<F, BITS>::new(hi)))
+            },
+        )?;
+
+        self.range_check(layouter.namespace(|| "range check lo"), &lo)?;
+        self.range_check(layouter.namespace(|| "range check hi"), &hi)?;
+
+        Ok((lo, hi))
+    }
+
+    fn rotl(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        n: u32,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+
+        let n = n % BITS as u32;
+        if n == 0 {
+            return Ok(a.clone());
+        }
+
+        let w = a.value_word();
+        let hi = w.map(|w| w >> (BITS as u32 - n));
+        let lo = w.map(|w| w << n);
+        let out = w.map(|w| w.rotate_left(n));
+        let hi_shifted = hi.map(|hi| hi << (BITS as u32 - n));
+
+        let (lo, out, hi_shifted) = layouter.assign_region(
+            || "rotl",
+            |mut region| {
+                config.q_rotl.enable(&mut region, 0)?;
+
+                region.assign_fixed(
+                    || "shift",
+                    config.shift,
+                    0,
+                    || Ok(F::from(1u64 << n)),
+                )?;
+
+                a.copy(|| "copy w", &mut region, config.advice[0], 0)?;
+
+                region.assign_advice(
+                    || "hi",
+                    config.advice[1],
+                    0,
+                    || hi.ok_or(Error::Synthesis),
+                )?;
+
+                let lo = region.assign_advice(
+                    || "lo",
+                    config.advice[2],
+                    0,
+                    || lo.ok_or(Error::Synthesis),
+                )?;
+
+                let out = region.assign_advice(
+                    || "out",
+                    config.advice[0],
+                    1,
+                    || out.ok_or(Error::Synthesis),
+                )?;
+
+                let hi_shifted = region.assign_advice(
+                    || "hi shifted",
+                    config.advice[1],
+                    1,
+                    || hi_shifted.ok_or(Error::Synthesis),
+                )?;
+
+                Ok((AssignedWord::<F, BITS>::new(lo), AssignedWord::<F, BITS>::new(out), AssignedWord::<F, BITS>::new(hi_shifted)))
+            },
+        )?;
+
+        self.range_check(layouter.namespace(|| "range check lo"), &lo)?;
+        self.range_check(layouter.namespace(|| "range check out"), &out)?;
+        self.range_check(layouter.namespace(|| "range check hi"), &hi_shifted)?;
+
+        Ok(out)
+    }
+
+    fn shr(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        n: u32,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+
+        if n == 0 {
+            return Ok(a.clone());
+        }
+        if n as usize >= BITS {
+            return self.constant(layouter.namespace(|| "shifted out"), Word::new(0));
+        }
+
+        let w = a.value_word();
+        let hi = w.map(|w| w >> n);
+        let lo = w.map(|w| Word::<BITS>::new(*w & ((1 << n) - 1)));
+        let lo_shifted = lo.map(|lo| lo << (BITS as u32 - n));
+
+        let (out, lo_shifted) = layouter.assign_region(
+            || "shr",
+            |mut region| {
+                config.q_shr.enable(&mut region, 0)?;
+
+                region.assign_fixed(
+                    || "shift",
+                    config.shift,
+                    0,
+                    || Ok(F::from(1u64 << n)),
+                )?;
+
+                a.copy(|| "copy w", &mut region, config.advice[0], 0)?;
+
+                let out = region.assign_advice(
+                    || "hi",
+                    config.advice[1],
+                    0,
+                    || hi.ok_or(Error::Synthesis),
+                )?;
+
+                region.assign_advice(
+                    || "lo",
+                    config.advice[2],
+                    0,
+                    || lo.ok_or(Error::Synthesis),
+                )?;
+
+                let lo_shifted = region.assign_advice(
+                    || "lo shifted",
+                    config.advice[0],
+                    1,
+                    || lo_shifted.ok_or(Error::Synthesis),
+                )?;
+
+                Ok((AssignedWord::<F, BITS>::new(out), AssignedWord::<F, BITS>::new(lo_shifted)))
+            },
+        )?;
+
+        self.range_check(layouter.namespace(|| "range check out"), &out)?;
+        self.range_check(layouter.namespace(|| "range check lo"), &lo_shifted)?;
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use std::convert::TryInto;
+
+    use super::{BitwiseChip, BitwiseConfig, BitwiseInstructions};
+
+    use crate::gadget::{
+        decompose::DecomposeChip,
+        xor::TableChip,
+    };
+    use crate::tamper::Tampering;
+    use crate::word::{AssignedWord, Word};
+
+    #[derive(Clone, Debug)]
+    pub struct Config<const BITS: usize, const LIMBS: usize> {
+        instance: Column<Instance>,
+        bitwise_config: BitwiseConfig<BITS, LIMBS>
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit<const BITS: usize, const LIMBS: usize> {
+        a: Option<Word<BITS>>,
+        b: Option<Word<BITS>>,
+    }
+
+    impl<F: FieldExt, const BITS: usize, const LIMBS: usize> plonk::Circuit<F> for Circuit<BITS, LIMBS> {
+        type Config = Config<BITS, LIMBS>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = DecomposeChip::<F, BITS, LIMBS>::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_config = BitwiseChip::<F, BITS, LIMBS>::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_config,
+                table_config
+            );
+
+            Config {
+                instance,
+                bitwise_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.bitwise_config.table_config.clone(), &mut layouter)?;
+            let chip = BitwiseChip::<F, BITS, LIMBS>::construct(config.bitwise_config.clone());
+
+            let a = chip.load_word(layouter.namespace(|| "load a"), self.a)?;
+            let b = chip.load_word(layouter.namespace(|| "load b"), self.b)?;
+
+            let last = BITS as u32 - 1;
+            let mul = chip.mul(layouter.namespace(|| "mul"), &a, &b)?;
+            let outputs: Vec<AssignedWord<F, BITS>> = vec![
+                chip.xor(layouter.namespace(|| "xor"), &a, &b)?,
+                chip.and(layouter.namespace(|| "and"), &a, &b)?,
+                chip.or(layouter.namespace(|| "or"), &a, &b)?,
+                chip.not(layouter.namespace(|| "not"), &a)?,
+                chip.add(layouter.namespace(|| "add"), &a, &b)?,
+                mul.0,
+                mul.1,
+                chip.rotl(layouter.namespace(|| "rotl 1"), &a, 1)?,
+                chip.rotl(layouter.namespace(|| "rotl 13"), &a, 13)?,
+                chip.rotl(layouter.namespace(|| "rotl last"), &a, last)?,
+                chip.rotr(layouter.namespace(|| "rotr 19"), &a, 19)?,
+                chip.shr(layouter.namespace(|| "shr 1"), &a, 1)?,
+                chip.shr(layouter.namespace(|| "shr 7"), &a, 7)?,
+                chip.shr(layouter.namespace(|| "shr last"), &a, last)?,
+                chip.shr(layouter.namespace(|| "shr 0"), &a, 0)?,
+                chip.shr(layouter.namespace(|| "shr all"), &a, BITS as u32)?,
+            ];
+
+            for (row, output) in outputs.iter().enumerate() {
+                layouter.constrain_instance(output.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    enum Op {
+        Xor,
+        And,
+        Or,
+        Not,
+        Rotl(u32),
+        Shr(u32),
+        Add,
+        Mul,
+    }
+
+    /// A single operation on `a` and `b`, small enough to tamper with every cell of
+    #[derive(Debug)]
+    pub struct OpCircuit<const BITS: usize, const LIMBS: usize> {
+        op: Op,
+        a: Option<Word<BITS>>,
+        b: Option<Word<BITS>>,
+    }
+
+    impl<F: FieldExt, const BITS: usize, const LIMBS: usize> plonk::Circuit<F> for OpCircuit<BITS, LIMBS> {
+        type Config = Config<BITS, LIMBS>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            OpCircuit {
+                op: self.op,
+                a: None,
+                b: None,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            <Circuit<BITS, LIMBS> as plonk::Circuit<F>>::configure(meta)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.bitwise_config.table_config.clone(), &mut layouter)?;
+            let chip = BitwiseChip::<F, BITS, LIMBS>::construct(config.bitwise_config.clone());
+
+            let a = chip.load_word(layouter.namespace(|| "load a"), self.a)?;
+            let b = chip.load_word(layouter.namespace(|| "load b"), self.b)?;
+
+            let outputs = match self.op {
+                Op::Xor => vec![chip.xor(layouter.namespace(|| "xor"), &a, &b)?],
+                Op::And => vec![chip.and(layouter.namespace(|| "and"), &a, &b)?],
+                Op::Or => vec![chip.or(layouter.namespace(|| "or"), &a, &b)?],
+                Op::Not => vec![chip.not(layouter.namespace(|| "not"), &a)?],
+                Op::Rotl(r) => vec![chip.rotl(layouter.namespace(|| "rotl"), &a, r)?],
+                Op::Shr(s) => vec![chip.shr(layouter.namespace(|| "shr"), &a, s)?],
+                Op::Add => vec![chip.add(layouter.namespace(|| "add"), &a, &b)?],
+                Op::Mul => {
+                    let (lo, hi) = chip.mul(layouter.namespace(|| "mul"), &a, &b)?;
+                    vec![lo, hi]
+                }
+            };
+
+            for (row, output) in outputs.iter().enumerate() {
+                layouter.constrain_instance(output.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn expected_32<F: FieldExt>(a: u32, b: u32) -> Vec<F> {
+        vec![
+            a ^ b,
+            a & b,
+            a | b,
+            !a,
+            a.wrapping_add(b),
+            (a as u64 * b as u64) as u32,
+            ((a as u64 * b as u64) >> 32) as u32,
+            a.rotate_left(1),
+            a.rotate_left(13),
+            a.rotate_left(31),
+            a.rotate_right(19),
+            a >> 1,
+            a >> 7,
+            a >> 31,
+            a,
+            0,
+        ]
+        .into_iter()
+        .map(|word| F::from(word as u64))
+        .collect()
+    }
+
+    fn expected_64<F: FieldExt>(a: u64, b: u64) -> Vec<F> {
+        vec![
+            a ^ b,
+            a & b,
+            a | b,
+            !a,
+            a.wrapping_add(b),
+            (a as u128 * b as u128) as u64,
+            ((a as u128 * b as u128) >> 64) as u64,
+            a.rotate_left(1),
+            a.rotate_left(13),
+            a.rotate_left(63),
+            a.rotate_right(19),
+            a >> 1,
+            a >> 7,
+            a >> 63,
+            a,
+            0,
+        ]
+        .into_iter()
+        .map(F::from)
+        .collect()
+    }
+
+    fn bitwise_test<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        for (a, b) in [(0, 0), (u32::MAX, 1), (u32::MAX, u32::MAX), (rng.gen(), rng.gen())] {
+            let circuit = Circuit::<32, 4> {
+                a: Some(Word::new(a as u64)),
+                b: Some(Word::new(b as u64)),
+            };
+
+            let prover = MockProver::<F>::run(k, &circuit, vec![expected_32(a, b)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn bitwise_wrong_output<F: FieldExt>() {
+        let k = 17;
+        let (a, b) = (0xf2345678u32, 0x9abcdef0u32);
+
+        let circuit = Circuit::<32, 4> {
+            a: Some(Word::new(a as u64)),
+            b: Some(Word::new(b as u64)),
+        };
+
+        let mut public_inputs = expected_32(a, b);
+        public_inputs[4] = F::from(a as u64 + b as u64);
+
+        let prover = MockProver::<F>::run(k, &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn bitwise_64_test<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        for (a, b) in [(0, 0), (u64::MAX, 1), (u64::MAX, u64::MAX), (rng.gen(), rng.gen())] {
+            let circuit = Circuit::<64, 8> {
+                a: Some(Word::new(a)),
+                b: Some(Word::new(b)),
+            };
+
+            let prover = MockProver::<F>::run(k, &circuit, vec![expected_64(a, b)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn bitwise_64_wrong_output<F: FieldExt>() {
+        let k = 17;
+        let (a, b) = (0xf123456789abcdefu64, 0xfedcba9876543210u64);
+
+        let circuit = Circuit::<64, 8> {
+            a: Some(Word::new(a)),
+            b: Some(Word::new(b)),
+        };
+
+        let mut public_inputs = expected_64(a, b);
+        public_inputs[4] = F::from_u128(a as u128 + b as u128);
+
+        let prover = MockProver::<F>::run(k, &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn mul_wrong_output<F: FieldExt>() {
+        let k = 17;
+        let (a, b) = (0xf2345678u32, 0x9abcdef0u32);
+
+        let circuit = Circuit::<32, 4> {
+            a: Some(Word::new(a as u64)),
+            b: Some(Word::new(b as u64)),
+        };
+
+        // the unreduced product as lo
+        let mut public_inputs = expected_32(a, b);
+        public_inputs[5] = F::from(a as u64 * b as u64);
+
+        let prover = MockProver::<F>::run(k, &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn bitwise_tampering<F: FieldExt>() {
+        let (a, b) = (0xf2345678u32, 0x9abcdef0u32);
+        let product = a as u64 * b as u64;
+
+        let ops = [
+            (Op::Xor, vec![a ^ b]),
+            (Op::And, vec![a & b]),
+            (Op::Or, vec![a | b]),
+            (Op::Not, vec![!a]),
+            (Op::Rotl(13), vec![a.rotate_left(13)]),
+            (Op::Shr(7), vec![a >> 7]),
+            (Op::Add, vec![a.wrapping_add(b)]),
+            (Op::Mul, vec![product as u32, (product >> 32) as u32]),
+        ];
+
+        for (op, expected) in ops {
+            let circuit = OpCircuit::<32, 4> {
+                op,
+                a: Some(Word::new(a as u64)),
+                b: Some(Word::new(b as u64)),
+            };
+            let expected = expected.into_iter().map(|word| F::from(word as u64)).collect();
+
+            Tampering::<F, _>::new(17, &circuit, vec![expected]).assert_sound();
+        }
+    }
+
+    pasta_tests!(bitwise_test, bitwise_wrong_output, bitwise_64_test, bitwise_64_wrong_output, mul_wrong_output, bitwise_tampering);
+}
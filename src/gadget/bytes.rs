@@ -0,0 +1,407 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{AssignedCell, Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector},
+    poly::Rotation,
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use crate::gadget::{
+    decompose::{DecomposeChip, DecomposeInstruction},
+    xor::{TableChip, TableConfig},
+};
+use crate::gates::Gate;
+use crate::word::{AssignedChunk, AssignedWord, Endianness};
+
+/// Bytes that fit into a single field element without wrapping
+pub const PACK_BYTES: usize = 31;
+
+/// Native counterpart of [`BytesChip::pack`], e.g. for computing public inputs
+pub fn pack<F: FieldExt>(bytes: &[u8], endianness: Endianness) -> F {
+    assert!(bytes.len() <= PACK_BYTES);
+
+    let fold = |acc: F, byte: &u8| acc * F::from(256) + F::from(*byte as u64);
+    match endianness {
+        Endianness::Big => bytes.iter().fold(F::zero(), fold),
+        Endianness::Little => bytes.iter().rev().fold(F::zero(), fold),
+    }
+}
+
+/// A byte string of range checked chunks
+#[derive(Clone, Debug, Default)]
+pub struct AssignedBytes<F: FieldExt>(Vec<AssignedChunk<F>>);
+
+impl<F: FieldExt> AssignedBytes<F> {
+    pub fn new(bytes: Vec<AssignedChunk<F>>) -> Self {
+        AssignedBytes(bytes)
+    }
+
+    pub fn bytes(&self) -> &[AssignedChunk<F>] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn value(&self) -> Option<Vec<u8>> {
+        self.0
+            .iter()
+            .map(|byte| byte.value_word().map(|byte| *byte as u8))
+            .collect()
+    }
+
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        AssignedBytes(self.0[range].to_vec())
+    }
+
+    pub fn concat(&self, other: &AssignedBytes<F>) -> Self {
+        AssignedBytes([self.0.as_slice(), other.0.as_slice()].concat())
+    }
+
+    /// Packs into `LIMBS` byte words, the length must be a multiple of `LIMBS`
+    pub fn to_words<const BITS: usize, const LIMBS: usize>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        decompose_chip: &DecomposeChip<F, BITS, LIMBS>,
+        endianness: Endianness,
+    ) -> Result<Vec<AssignedWord<F, BITS>>, Error> {
+        assert_eq!(self.len() % LIMBS, 0, "{} bytes do not fill {} bit words", self.len(), BITS);
+
+        self.0
+            .chunks(LIMBS)
+            .enumerate()
+            .map(|(i, bytes)| {
+                let chunks: [AssignedChunk<F>; LIMBS] = bytes.to_vec().try_into().unwrap();
+                decompose_chip.compose_with(layouter.namespace(|| format!("word {}", i)), chunks, endianness)
+            })
+            .collect()
+    }
+
+    /// Unpacks words into their bytes, each word is range checked by the decomposition
+    pub fn from_words<const BITS: usize, const LIMBS: usize>(
+        mut layouter: impl Layouter<F>,
+        decompose_chip: &DecomposeChip<F, BITS, LIMBS>,
+        words: &[AssignedWord<F, BITS>],
+        endianness: Endianness,
+    ) -> Result<Self, Error> {
+        let mut bytes = Vec::with_capacity(words.len() * LIMBS);
+
+        for (i, word) in words.iter().enumerate() {
+            let chunks = decompose_chip.decompose_with(layouter.namespace(|| format!("word {}", i)), word.clone(), endianness)?;
+            bytes.extend(chunks);
+        }
+
+        Ok(AssignedBytes(bytes))
+    }
+}
+
+impl<F: FieldExt> From<Vec<AssignedChunk<F>>> for AssignedBytes<F> {
+    fn from(bytes: Vec<AssignedChunk<F>>) -> Self {
+        AssignedBytes(bytes)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BytesConfig {
+    pub q_pack: Selector,
+    pub advice: [Column<Advice>; 2],
+    pub table_config: TableConfig,
+}
+
+#[derive(Clone, Debug)]
+pub struct BytesChip<F> {
+    config: BytesConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for BytesChip<F> {
+    type Config = BytesConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> BytesChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        constants: Column<Fixed>,
+        table_config: TableConfig,
+    ) -> BytesConfig {
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+        meta.enable_constant(constants);
+
+        let config = BytesConfig {
+            q_pack: meta.selector(),
+            advice,
+            table_config,
+        };
+
+        // byte, acc
+        meta.create_gate("pack", |meta| {
+            let q_pack = meta.query_selector(config.q_pack);
+
+            let byte = meta.query_advice(config.advice[0], Rotation::cur());
+            let acc = meta.query_advice(config.advice[1], Rotation::cur());
+            let acc_prev = meta.query_advice(config.advice[1], Rotation::prev());
+
+            Gate::pack(q_pack, acc_prev, acc, byte)
+        });
+
+        config
+    }
+
+    pub fn construct(config: BytesConfig) -> Self {
+        BytesChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.table_config.clone())
+    }
+
+    /// Witnesses bytes in lookup rows of the xor table, which range checks them
+    pub fn load_bytes(
+        &self,
+        layouter: impl Layouter<F>,
+        bytes: &[Option<u8>],
+    ) -> Result<AssignedBytes<F>, Error> {
+        let bytes = self.table_chip().load_chunks(layouter, bytes)?;
+        Ok(AssignedBytes(bytes))
+    }
+
+    /// Packs up to [`PACK_BYTES`] bytes into a single field element, e.g. to expose
+    /// them as one public input. With big-endian order the first byte is the most significant.
+    pub fn pack(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &AssignedBytes<F>,
+        endianness: Endianness,
+    ) -> Result<AssignedCell<F, F>, Error> {
+        let config = self.config();
+        assert!(bytes.len() <= PACK_BYTES, "{} bytes do not fit into a field element", bytes.len());
+
+        let mut ordered = bytes.bytes().to_vec();
+        if endianness == Endianness::Little {
+            ordered.reverse();
+        }
+
+        layouter.assign_region(
+            || "pack",
+            |mut region| {
+                // acc starts from zero on the first row
+                let mut acc = region.assign_advice_from_constant(
+                    || "acc 0",
+                    config.advice[1],
+                    0,
+                    F::zero(),
+                )?;
+
+                for (i, byte) in ordered.iter().enumerate() {
+                    let row = i + 1;
+                    config.q_pack.enable(&mut region, row)?;
+
+                    byte.copy(|| format!("byte {}", i), &mut region, config.advice[0], row)?;
+
+                    let value = acc.value().zip(byte.value_word())
+                        .map(|(acc, byte)| *acc * F::from(256) + F::from(*byte));
+
+                    acc = region.assign_advice(
+                        || format!("acc {}", row),
+                        config.advice[1],
+                        row,
+                        || value.ok_or(Error::Synthesis),
+                    )?;
+                }
+
+                Ok(acc)
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use std::cmp;
+    use std::convert::TryInto;
+
+    use super::{pack, AssignedBytes, BytesChip, BytesConfig, PACK_BYTES};
+
+    use crate::gadget::{
+        decompose::{Decompose32Chip, Decompose32Config},
+        xor::TableChip,
+    };
+    use crate::tamper::Tampering;
+    use crate::word::Endianness;
+
+    const LEN: usize = 40;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        bytes_config: BytesConfig,
+        decompose_config: Decompose32Config,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        bytes: Vec<Option<u8>>,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                bytes: vec![None; self.bytes.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bytes_config = BytesChip::configure(meta, advice[0..2].try_into().unwrap(), constants, table_config);
+
+            Config {
+                instance,
+                bytes_config,
+                decompose_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.bytes_config.table_config.clone(), &mut layouter)?;
+            let chip = BytesChip::<F>::construct(config.bytes_config.clone());
+            let decompose_chip = Decompose32Chip::<F>::construct(config.decompose_config.clone());
+
+            let bytes = chip.load_bytes(layouter.namespace(|| "load"), &self.bytes)?;
+
+            // swap the two halves
+            let half = bytes.len() / 2;
+            let swapped = bytes.slice(half..bytes.len()).concat(&bytes.slice(0..half));
+
+            let words_be = swapped.to_words(layouter.namespace(|| "words be"), &decompose_chip, Endianness::Big)?;
+            let words_le = swapped.to_words(layouter.namespace(|| "words le"), &decompose_chip, Endianness::Little)?;
+            let unpacked = AssignedBytes::from_words(layouter.namespace(|| "unpack"), &decompose_chip, &words_be, Endianness::Big)?;
+
+            let packed = swapped.slice(0..cmp::min(bytes.len(), PACK_BYTES));
+            let packed_be = chip.pack(layouter.namespace(|| "pack be"), &packed, Endianness::Big)?;
+            let packed_le = chip.pack(layouter.namespace(|| "pack le"), &packed, Endianness::Little)?;
+
+            let cells = words_be.iter().map(|word| word.cell())
+                .chain(words_le.iter().map(|word| word.cell()))
+                .chain(unpacked.bytes().iter().map(|byte| byte.cell()))
+                .chain([packed_be.cell(), packed_le.cell()]);
+
+            for (row, cell) in cells.enumerate() {
+                layouter.constrain_instance(cell, config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn expected<F: FieldExt>(bytes: &[u8]) -> Vec<F> {
+        let half = bytes.len() / 2;
+        let swapped = [&bytes[half..], &bytes[..half]].concat();
+        let packed = &swapped[..cmp::min(bytes.len(), PACK_BYTES)];
+
+        let words_be = swapped.chunks(4).map(|word| u32::from_be_bytes(word.try_into().unwrap()));
+        let words_le = swapped.chunks(4).map(|word| u32::from_le_bytes(word.try_into().unwrap()));
+
+        words_be
+            .chain(words_le)
+            .map(|word| F::from(word as u64))
+            .chain(swapped.iter().map(|byte| F::from(*byte as u64)))
+            .chain([pack(packed, Endianness::Big), pack(packed, Endianness::Little)])
+            .collect()
+    }
+
+    fn circuit(bytes: &[u8]) -> Circuit {
+        Circuit {
+            bytes: bytes.iter().copied().map(Some).collect(),
+        }
+    }
+
+    fn native_pack<F: FieldExt>() {
+        assert_eq!(pack::<F>(&[0x01, 0x02], Endianness::Big), F::from(0x0102));
+        assert_eq!(pack::<F>(&[0x01, 0x02], Endianness::Little), F::from(0x0201));
+        assert_eq!(pack::<F>(&[], Endianness::Big), F::zero());
+    }
+
+    fn bytes_test<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        let bytes: Vec<u8> = (0..LEN).map(|_| rng.gen()).collect();
+
+        let prover = MockProver::<F>::run(k, &circuit(&bytes), vec![expected(&bytes)]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    fn bytes_wrong_packing<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        let bytes: Vec<u8> = (0..LEN).map(|_| rng.gen()).collect();
+
+        // the big-endian packing claimed as little-endian
+        let mut public_inputs = expected(&bytes);
+        let last = public_inputs.len() - 1;
+        public_inputs[last] = public_inputs[last - 1];
+
+        let prover = MockProver::<F>::run(k, &circuit(&bytes), vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn bytes_tampering<F: FieldExt>() {
+        // a single word reaches every kind of cell
+        let bytes = [0x12, 0x34, 0x56, 0x78];
+        Tampering::<F, _>::new(17, &circuit(&bytes), vec![expected(&bytes)]).assert_sound();
+    }
+
+    pasta_tests!(native_pack, bytes_test, bytes_wrong_packing, bytes_tampering);
+}
@@ -0,0 +1,269 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter, Region},
+    plonk::{Advice, Column, ConstraintSystem, Error},
+};
+use std::marker::PhantomData;
+
+use crate::word::{Chunk, AssignedChunk};
+use crate::gadget::lookup::LookupTable;
+use crate::gadget::xor::{Inputs, Table, TableConfig};
+
+const CHI_BITS: usize = 8;
+
+fn chi_entry(row: usize) -> [u64; 3] {
+    let (l, r) = ((row >> CHI_BITS) as u64, (row & ((1 << CHI_BITS) - 1)) as u64);
+    [l, r, !l & r]
+}
+
+/// Rows of (l, r, !l & r) over every pair of bytes
+const CHI_TABLE: LookupTable = LookupTable {
+    name: "chi table",
+    len: 1 << (2 * CHI_BITS),
+    row: chi_entry,
+};
+
+/// Lookup table of (x, y, !x & y) over bytes, the nonlinear part of Keccak's χ step
+#[derive(Clone, Debug)]
+pub struct ChiTableChip<F: FieldExt> {
+    config: TableConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for ChiTableChip<F> {
+    type Config = TableConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> ChiTableChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        input_x: Column<Advice>,
+        input_y: Column<Advice>,
+        input_z: Column<Advice>,
+    ) -> <Self as Chip<F>>::Config {
+        let [table_x, table_y, table_z] = CHI_TABLE.configure(meta, [input_x, input_y, input_z], None);
+
+        TableConfig {
+            input: Inputs {
+                x: input_x,
+                y: input_y,
+                z: input_z,
+            },
+            table: Table {
+                x: table_x,
+                y: table_y,
+                z: table_z,
+            },
+        }
+    }
+
+    pub fn construct(config: TableConfig) -> Self {
+        ChiTableChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: TableConfig,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<<Self as Chip<F>>::Loaded, Error> {
+        CHI_TABLE.load(layouter, config.table.columns())
+    }
+
+    /// Copies `x` and `y` into the lookup row and witnesses `z = !x & y`.
+    pub fn chi_row(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        x: &AssignedChunk<F>,
+        y: &AssignedChunk<F>,
+    ) -> Result<AssignedChunk<F>, Error> {
+        let config = self.config();
+
+        x.copy(|| format!("x: {}", row), region, config.input.x, row)?;
+        y.copy(|| format!("y: {}", row), region, config.input.y, row)?;
+
+        let z = x.value_word().zip(y.value_word())
+            .map(|(x, y)| Chunk::new(!*x & *y));
+
+        let assigned = region.assign_advice(
+            || format!("z: {}", row),
+            config.input.z,
+            row,
+            || z.ok_or(Error::Synthesis)
+        )?;
+
+        Ok(AssignedChunk::new(assigned))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChiTableChip;
+
+    use halo2::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance},
+    };
+
+    use crate::gadget::xor::TableConfig;
+    use crate::tamper::Tampering;
+    use crate::word::{AssignedChunk, Chunk};
+
+    #[derive(Clone, Debug)]
+    struct MyCircuit {
+        rows: Vec<(Option<u8>, Option<u8>, Option<u8>)>,
+    }
+
+    impl<F: FieldExt> Circuit<F> for MyCircuit {
+        type Config = TableConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            MyCircuit {
+                rows: vec![(None, None, None); self.rows.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let input_x = meta.advice_column();
+            let input_y = meta.advice_column();
+            let input_z = meta.advice_column();
+
+            ChiTableChip::configure(meta, input_x, input_y, input_z)
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            ChiTableChip::load(config.clone(), &mut layouter)?;
+
+            layouter.assign_region(
+                || "chi rows",
+                |mut region| {
+                    for (row, (x, y, z)) in self.rows.iter().enumerate() {
+                        for (column, value) in [
+                            (config.input.x, x),
+                            (config.input.y, y),
+                            (config.input.z, z),
+                        ] {
+                            region.assign_advice(
+                                || format!("row {}", row),
+                                column,
+                                row,
+                                || value.map(|value| Chunk::new(value as u64)).ok_or(Error::Synthesis),
+                            )?;
+                        }
+                    }
+                    Ok(())
+                },
+            )?;
+
+            Ok(())
+        }
+    }
+
+    fn circuit(rows: &[(u8, u8, u8)]) -> MyCircuit {
+        MyCircuit {
+            rows: rows.iter().map(|(x, y, z)| (Some(*x), Some(*y), Some(*z))).collect(),
+        }
+    }
+
+    fn chi_table<F: FieldExt>() {
+        let circuit = circuit(&[
+            (0, 0, 0),
+            (0b00001111, 0b01010101, 0b01010000),
+            (0xff, 0xff, 0),
+            (0, 0xff, 0xff),
+        ]);
+
+        let prover = MockProver::<F>::run(17, &circuit, vec![]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    fn chi_table_wrong_row<F: FieldExt>() {
+        let circuit = circuit(&[(0b00001111, 0b01010101, 0b01011111)]);
+
+        let prover = MockProver::<F>::run(17, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    /// Lays `rows` out through `chi_row`, copying `x` and `y` in from a column outside the
+    /// lookup and exposing `z`
+    #[derive(Clone, Debug)]
+    struct ChiRowCircuit {
+        rows: Vec<(Option<u8>, Option<u8>)>,
+    }
+
+    impl<F: FieldExt> Circuit<F> for ChiRowCircuit {
+        type Config = (Column<Instance>, Column<Advice>, TableConfig);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            ChiRowCircuit {
+                rows: vec![(None, None); self.rows.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let input_x = meta.advice_column();
+            let input_y = meta.advice_column();
+            let input_z = meta.advice_column();
+            let advice = meta.advice_column();
+            meta.enable_equality(advice.into());
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            (instance, advice, ChiTableChip::configure(meta, input_x, input_y, input_z))
+        }
+
+        fn synthesize(
+            &self,
+            (instance, advice, config): Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            ChiTableChip::load(config.clone(), &mut layouter)?;
+            let chip = ChiTableChip::<F>::construct(config.clone());
+
+            for (row, (x, y)) in self.rows.iter().enumerate() {
+                let x = AssignedChunk::assign_word(layouter.namespace(|| format!("x {}", row)), advice, x.map(|x| Chunk::new(x as u64)))?;
+                let y = AssignedChunk::assign_word(layouter.namespace(|| format!("y {}", row)), advice, y.map(|y| Chunk::new(y as u64)))?;
+
+                let z = layouter.assign_region(
+                    || format!("chi row {}", row),
+                    |mut region| chip.chi_row(&mut region, 0, &x, &y),
+                )?;
+                layouter.constrain_instance(z.cell(), instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn chi_row_tampering<F: FieldExt>() {
+        let rows = [(0, 0), (0b00001111, 0b01010101), (0xff, 0xff), (0, 0xff)];
+        let circuit = ChiRowCircuit {
+            rows: rows.iter().map(|(x, y)| (Some(*x), Some(*y))).collect(),
+        };
+        let expected = rows.iter().map(|(x, y)| F::from((!x & y) as u64)).collect();
+
+        Tampering::<F, _>::new(17, &circuit, vec![expected]).assert_sound();
+    }
+
+    pasta_tests!(chi_table, chi_table_wrong_row, chi_row_tampering);
+}
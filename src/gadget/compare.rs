@@ -0,0 +1,438 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+use crate::gadget::decompose::{DecomposeChip, DecomposeConfig, DecomposeInstruction};
+use crate::gates::Gate;
+use crate::word::{AssignedBit, AssignedWord, Bit, Word};
+
+/// Comparisons of range checked `BITS` wide words, the results are constrained to 0 or 1.
+pub trait CompareInstructions<F: FieldExt, const BITS: usize> {
+    fn is_zero(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error>;
+
+    fn eq(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error>;
+
+    fn lt(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error>;
+
+    fn le(
+        &self,
+        layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error>;
+}
+
+#[derive(Clone, Debug)]
+pub struct CompareConfig<const BITS: usize, const LIMBS: usize> {
+    pub q_eq: Selector,
+    pub q_lt: Selector,
+    pub q_not: Selector,
+    pub advice: [Column<Advice>; 3],
+    pub decompose_config: DecomposeConfig<BITS, LIMBS>,
+}
+
+pub type Compare32Config = CompareConfig<32, 4>;
+
+#[derive(Clone, Debug)]
+pub struct CompareChip<F, const BITS: usize, const LIMBS: usize> {
+    config: CompareConfig<BITS, LIMBS>,
+    _marker: PhantomData<F>,
+}
+
+pub type Compare32Chip<F> = CompareChip<F, 32, 4>;
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> Chip<F> for CompareChip<F, BITS, LIMBS> {
+    type Config = CompareConfig<BITS, LIMBS>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> CompareChip<F, BITS, LIMBS> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        constants: Column<Fixed>,
+        decompose_config: DecomposeConfig<BITS, LIMBS>,
+    ) -> CompareConfig<BITS, LIMBS> {
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+        meta.enable_constant(constants);
+
+        let config = CompareConfig {
+            q_eq: meta.selector(),
+            q_lt: meta.selector(),
+            q_not: meta.selector(),
+            advice,
+            decompose_config,
+        };
+
+        meta.create_gate("eq", |meta| {
+            let q_eq = meta.query_selector(config.q_eq);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let b = meta.query_advice(config.advice[1], Rotation::cur());
+            let inv = meta.query_advice(config.advice[2], Rotation::cur());
+            let out = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::is_equal(q_eq, a, b, inv, out)
+        });
+
+        meta.create_gate("lt", |meta| {
+            let q_lt = meta.query_selector(config.q_lt);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let b = meta.query_advice(config.advice[1], Rotation::cur());
+            let borrow = meta.query_advice(config.advice[2], Rotation::cur());
+            let diff = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::less_than(q_lt, a, b, borrow, diff, BITS as u32)
+        });
+
+        meta.create_gate("not", |meta| {
+            let q_not = meta.query_selector(config.q_not);
+
+            let a = meta.query_advice(config.advice[0], Rotation::cur());
+            let out = meta.query_advice(config.advice[1], Rotation::cur());
+
+            Gate::not(q_not, a, out, 1)
+        });
+
+        config
+    }
+
+    pub fn construct(config: CompareConfig<BITS, LIMBS>) -> Self {
+        CompareChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    fn decompose_chip(&self) -> DecomposeChip<F, BITS, LIMBS> {
+        DecomposeChip::construct(self.config.decompose_config.clone())
+    }
+
+    /// 1 - a for a bit
+    fn not(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedBit<F>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let config = self.config();
+        let out = a.value_word().map(|a| !a);
+
+        layouter.assign_region(
+            || "not",
+            |mut region| {
+                config.q_not.enable(&mut region, 0)?;
+
+                a.copy(|| "copy a", &mut region, config.advice[0], 0)?;
+
+                let assigned = region.assign_advice(
+                    || "out",
+                    config.advice[1],
+                    0,
+                    || out.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedBit::new(assigned))
+            },
+        )
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> CompareInstructions<F, BITS> for CompareChip<F, BITS, LIMBS> {
+    fn is_zero(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let config = self.config();
+
+        let zero = layouter.assign_region(
+            || "zero",
+            |mut region| {
+                let assigned = region.assign_advice_from_constant(
+                    || "zero",
+                    config.advice[0],
+                    0,
+                    Word::<BITS>::new(0),
+                )?;
+
+                Ok(AssignedWord::<F, BITS>::new(assigned))
+            },
+        )?;
+
+        self.eq(layouter, a, &zero)
+    }
+
+    fn eq(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let config = self.config();
+
+        let diff = a.value_word().zip(b.value_word())
+            .map(|(a, b)| F::from(*a) - F::from(*b));
+        let inv = diff.map(|diff| Option::from(diff.invert()).unwrap_or_else(F::zero));
+        let out = diff.map(|diff| Bit::new(bool::from(diff.is_zero()) as u64));
+
+        layouter.assign_region(
+            || "eq",
+            |mut region| {
+                config.q_eq.enable(&mut region, 0)?;
+
+                a.copy(|| "copy a", &mut region, config.advice[0], 0)?;
+                b.copy(|| "copy b", &mut region, config.advice[1], 0)?;
+
+                region.assign_advice(
+                    || "inv",
+                    config.advice[2],
+                    0,
+                    || inv.ok_or(Error::Synthesis),
+                )?;
+
+                let assigned = region.assign_advice(
+                    || "out",
+                    config.advice[0],
+                    1,
+                    || out.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedBit::new(assigned))
+            },
+        )
+    }
+
+    fn lt(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let config = self.config();
+
+        let borrow = a.value_word().zip(b.value_word())
+            .map(|(a, b)| Bit::new((*a < *b) as u64));
+        let diff = a.value_word().zip(b.value_word())
+            .map(|(a, b)| Word::<BITS>::new((((*a as u128) | 1 << BITS) - *b as u128) as u64 & Word::<BITS>::MASK));
+
+        let (borrow, diff) = layouter.assign_region(
+            || "lt",
+            |mut region| {
+                config.q_lt.enable(&mut region, 0)?;
+
+                a.copy(|| "copy a", &mut region, config.advice[0], 0)?;
+                b.copy(|| "copy b", &mut region, config.advice[1], 0)?;
+
+                let borrow = region.assign_advice(
+                    || "borrow",
+                    config.advice[2],
+                    0,
+                    || borrow.ok_or(Error::Synthesis),
+                )?;
+
+                let diff = region.assign_advice(
+                    || "diff",
+                    config.advice[0],
+                    1,
+                    || diff.ok_or(Error::Synthesis),
+                )?;
+
+                Ok((AssignedBit::new(borrow), AssignedWord::<F, BITS>::new(diff)))
+            },
+        )?;
+
+        self.decompose_chip().decompose(layouter.namespace(|| "range check diff"), diff)?;
+
+        Ok(borrow)
+    }
+
+    fn le(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let gt = self.lt(layouter.namespace(|| "gt"), b, a)?;
+        self.not(layouter.namespace(|| "not gt"), &gt)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+
+    use super::{Compare32Chip, Compare32Config, CompareInstructions};
+
+    use crate::gadget::decompose::{Decompose32Chip, DecomposeInstruction};
+    use crate::tamper::{Change, Tampering};
+    use crate::word::{AssignedWord32, Word32};
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        compare_config: Compare32Config,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        a: Option<Word32>,
+        b: Option<Word32>,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 3] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let decompose_config = Decompose32Chip::configure(meta, advice);
+            let compare_config = Compare32Chip::configure(meta, advice, constants, decompose_config);
+
+            Config {
+                instance,
+                compare_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let chip = Compare32Chip::<F>::construct(config.compare_config.clone());
+            let decompose_chip = Decompose32Chip::<F>::construct(config.compare_config.decompose_config.clone());
+
+            let mut load = |name: &'static str, value: Option<Word32>| {
+                let word = AssignedWord32::assign_word(layouter.namespace(|| name), config.compare_config.advice[0], value)?;
+                decompose_chip.decompose(layouter.namespace(|| "range check"), word.clone())?;
+                Ok::<_, Error>(word)
+            };
+
+            let a = load("load a", self.a)?;
+            let b = load("load b", self.b)?;
+
+            let outputs = [
+                chip.is_zero(layouter.namespace(|| "is_zero"), &a)?,
+                chip.eq(layouter.namespace(|| "eq"), &a, &b)?,
+                chip.lt(layouter.namespace(|| "lt"), &a, &b)?,
+                chip.le(layouter.namespace(|| "le"), &a, &b)?,
+            ];
+
+            for (row, output) in outputs.iter().enumerate() {
+                layouter.constrain_instance(output.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn expected<F: FieldExt>(a: u32, b: u32) -> Vec<F> {
+        [a == 0, a == b, a < b, a <= b]
+            .iter()
+            .map(|bit| F::from(*bit as u64))
+            .collect()
+    }
+
+    fn circuit(a: u32, b: u32) -> Circuit {
+        Circuit {
+            a: Some(Word32::new(a as u64)),
+            b: Some(Word32::new(b as u64)),
+        }
+    }
+
+    fn compare_test<F: FieldExt>() {
+        let k = 6;
+        let mut rng = rand::thread_rng();
+
+        let max = u32::MAX;
+        let pairs = [
+            (0, 0),
+            (0, 1),
+            (1, 0),
+            (1, 1),
+            (max, max),
+            (max, 0),
+            (0, max),
+            (max - 1, max),
+            (max, max - 1),
+            (rng.gen(), rng.gen()),
+        ];
+
+        for (a, b) in pairs {
+            let prover = MockProver::<F>::run(k, &circuit(a, b), vec![expected(a, b)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "a = {}, b = {}", a, b);
+        }
+    }
+
+    fn compare_wrong_output<F: FieldExt>() {
+        let k = 6;
+
+        // claims 2 < 1
+        let prover = MockProver::<F>::run(k, &circuit(2, 1), vec![expected(1, 2)]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn compare_tampering<F: FieldExt>() {
+        let k = 6;
+
+        Tampering::new(k, &circuit(3, 9), vec![expected::<F>(3, 9)]).assert_sound();
+
+        // equal inputs leave the inverse of their difference unconstrained, it is a hint
+        // for the `a != b` case only
+        let circuit = circuit(7, 7);
+        let harness = Tampering::new(k, &circuit, vec![expected::<F>(7, 7)]);
+        let cells = harness.cells().iter().filter(|cell| cell.annotation != "inv");
+        harness.assert_detected(cells, Change::Add(F::one()));
+    }
+
+    pasta_tests!(compare_test, compare_wrong_output, compare_tampering);
+}
@@ -0,0 +1,426 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, TableColumn},
+    poly::Rotation,
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::{
+    decompose::{Decompose32Chip, Decompose32Config, DecomposeInstruction},
+    xor::{TableChip, TableConfig},
+};
+use crate::word::{AssignedChunk, AssignedWord32, Chunk};
+
+/// Reflected IEEE 802.3 polynomial
+const POLYNOMIAL: u32 = 0xedb88320;
+
+/// CRC of every byte value, `table()[i]` is the checksum register after shifting in `i`
+pub fn table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut crc = i as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { crc >> 1 ^ POLYNOMIAL } else { crc >> 1 };
+        }
+        *entry = crc;
+    }
+    table
+}
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let table = table();
+    let crc = bytes
+        .iter()
+        .fold(0xffffffffu32, |crc, byte| crc >> 8 ^ table[((crc ^ *byte as u32) & 0xff) as usize]);
+    !crc
+}
+
+/// Lookup rows of (i, table[i]) with the entry split into little-endian bytes
+#[derive(Clone, Debug)]
+pub struct Crc32TableConfig {
+    pub input_index: Column<Advice>,
+    pub input_entry: [Column<Advice>; 4],
+    pub table_index: TableColumn,
+    pub table_entry: [TableColumn; 4],
+}
+
+#[derive(Clone, Debug)]
+pub struct Crc32TableChip<F: FieldExt> {
+    config: Crc32TableConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for Crc32TableChip<F> {
+    type Config = Crc32TableConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> Crc32TableChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        input_index: Column<Advice>,
+        input_entry: [Column<Advice>; 4],
+    ) -> <Self as Chip<F>>::Config {
+        // we must enable equality so that chunks can be copied into lookup rows
+        meta.enable_equality(input_index.into());
+        for input in input_entry.iter() {
+            meta.enable_equality((*input).into());
+        }
+
+        let table_index = meta.lookup_table_column();
+        let table_entry = [
+            meta.lookup_table_column(),
+            meta.lookup_table_column(),
+            meta.lookup_table_column(),
+            meta.lookup_table_column(),
+        ];
+
+        meta.lookup(|meta| {
+            let index = meta.query_advice(input_index, Rotation::cur());
+
+            std::iter::once((index, table_index))
+                .chain(input_entry.iter().zip(table_entry.iter()).map(|(input, table)| {
+                    (meta.query_advice(*input, Rotation::cur()), *table)
+                }))
+                .collect()
+        });
+
+        Crc32TableConfig {
+            input_index,
+            input_entry,
+            table_index,
+            table_entry,
+        }
+    }
+
+    pub fn construct(config: Crc32TableConfig) -> Self {
+        Crc32TableChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: Crc32TableConfig,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<<Self as Chip<F>>::Loaded, Error> {
+        layouter.assign_table(
+            || "crc32 table",
+            |mut table| {
+                for (row_offset, entry) in self::table().iter().enumerate() {
+                    table.assign_cell(
+                        || format!("crc_index_col row {}", row_offset),
+                        config.table_index,
+                        row_offset,
+                        || Ok(F::from(row_offset as u64)),
+                    )?;
+
+                    for (column, byte) in config.table_entry.iter().zip(entry.to_le_bytes()) {
+                        table.assign_cell(
+                            || format!("crc_entry_col row {}", row_offset),
+                            *column,
+                            row_offset,
+                            || Ok(F::from(byte as u64)),
+                        )?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Crc32Config {
+    pub crc32_table_config: Crc32TableConfig,
+    pub table_config: TableConfig,
+    pub decompose_config: Decompose32Config,
+}
+
+/// Table driven CRC-32: every byte xors into the low byte of the register, which then
+/// indexes the CRC table, the entry is xored into the register shifted down by a byte.
+#[derive(Clone, Debug)]
+pub struct Crc32Chip<F> {
+    config: Crc32Config,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for Crc32Chip<F> {
+    type Config = Crc32Config;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> Crc32Chip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        constants: Column<Fixed>,
+        crc32_table_config: Crc32TableConfig,
+        table_config: TableConfig,
+        decompose_config: Decompose32Config,
+    ) -> Crc32Config {
+        meta.enable_constant(constants);
+
+        Crc32Config {
+            crc32_table_config,
+            table_config,
+            decompose_config,
+        }
+    }
+
+    pub fn construct(config: Crc32Config) -> Self {
+        Crc32Chip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: Crc32Config,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.table_config.clone(), layouter)?;
+        Crc32TableChip::load(config.crc32_table_config, layouter)
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.table_config.clone())
+    }
+
+    /// Witnesses the bytes of a message, range checked through the xor table
+    pub fn load_bytes(
+        &self,
+        layouter: impl Layouter<F>,
+        bytes: &[Option<u8>],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        self.table_chip().load_chunks(layouter, bytes)
+    }
+
+    /// CRC-32 of range checked `bytes`
+    pub fn checksum(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[AssignedChunk<F>],
+    ) -> Result<AssignedWord32<F>, Error> {
+        let config = self.config();
+        let table_chip = self.table_chip();
+        let crc_table = table();
+
+        let crc = layouter.assign_region(
+            || "crc32",
+            |mut region| {
+                // register as little-endian bytes, kept out of the lookup inputs
+                // since 0xffffffff is not a table entry
+                let mut crc = (0..4)
+                    .map(|i| {
+                        region.assign_advice_from_constant(
+                            || format!("initial crc {}", i),
+                            config.decompose_config.advice[i % 3],
+                            i / 3,
+                            Chunk::new(0xff),
+                        )
+                        .map(AssignedChunk::new)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                let mut xor_row = 0;
+                for (i, byte) in bytes.iter().enumerate() {
+                    let index = table_chip.xor_row(&mut region, xor_row, &crc[0], byte)?;
+                    xor_row += 1;
+
+                    index.copy(
+                        || format!("index {}", i),
+                        &mut region,
+                        config.crc32_table_config.input_index,
+                        i,
+                    )?;
+
+                    let entry = index.value_word().map(|index| crc_table[*index as usize].to_le_bytes());
+                    let entry = config
+                        .crc32_table_config
+                        .input_entry
+                        .iter()
+                        .enumerate()
+                        .map(|(j, column)| {
+                            region.assign_advice(
+                                || format!("entry {} byte {}", i, j),
+                                *column,
+                                i,
+                                || entry.map(|entry| Chunk::new(entry[j] as u64)).ok_or(Error::Synthesis),
+                            )
+                            .map(AssignedChunk::new)
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?;
+
+                    // (crc >> 8) ^ entry
+                    let mut next = Vec::with_capacity(4);
+                    for j in 0..3 {
+                        next.push(table_chip.xor_row(&mut region, xor_row, &crc[j + 1], &entry[j])?);
+                        xor_row += 1;
+                    }
+                    next.push(entry[3].clone());
+
+                    crc = next;
+                }
+
+                // final inversion
+                crc.iter()
+                    .map(|byte| {
+                        let inverted = table_chip.xor_constant_row(&mut region, xor_row, byte, Chunk::new(0xff));
+                        xor_row += 1;
+                        inverted
+                    })
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?;
+
+        let decompose_chip = Decompose32Chip::<F>::construct(config.decompose_config.clone());
+        decompose_chip.compose(layouter.namespace(|| "compose crc"), crc.try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use std::convert::TryInto;
+
+    use super::{crc32, Crc32Chip, Crc32Config, Crc32TableChip};
+
+    use crate::gadget::{
+        decompose::Decompose32Chip,
+        xor::TableChip,
+    };
+    use crate::tamper::Tampering;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        crc32_config: Crc32Config
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        message: Vec<Option<u8>>,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                message: vec![None; self.message.len()]
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 11] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let crc32_table_config = Crc32TableChip::configure(meta, advice[6], advice[7..11].try_into().unwrap());
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let crc32_config = Crc32Chip::configure(meta, constants, crc32_table_config, table_config, decompose_config);
+
+            Config {
+                instance,
+                crc32_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            Crc32Chip::<F>::load(config.crc32_config.clone(), &mut layouter)?;
+            let chip = Crc32Chip::<F>::construct(config.crc32_config.clone());
+
+            let bytes = chip.load_bytes(layouter.namespace(|| "load message"), &self.message)?;
+            let crc = chip.checksum(layouter.namespace(|| "crc32"), &bytes)?;
+
+            layouter.constrain_instance(crc.cell(), config.instance, 0)
+        }
+    }
+
+    fn run<F: FieldExt>(message: &[u8], expected: u32) -> MockProver<F> {
+        let circuit = Circuit {
+            message: message.iter().map(|byte| Some(*byte)).collect()
+        };
+
+        MockProver::<F>::run(17, &circuit, vec![vec![F::from(expected as u64)]]).unwrap()
+    }
+
+    #[test]
+    fn native_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414fa339);
+    }
+
+    fn crc32_test<F: FieldExt>() {
+        let mut rng = rand::thread_rng();
+        let random: Vec<u8> = (0..rng.gen_range(1..100)).map(|_| rng.gen()).collect();
+
+        for message in [&b""[..], b"123456789", &random] {
+            let prover = run::<F>(message, crc32(message));
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn crc32_wrong_checksum<F: FieldExt>() {
+        let prover = run::<F>(b"123456789", crc32(b"123456780"));
+        assert!(prover.verify().is_err());
+    }
+
+    fn crc32_tampering<F: FieldExt>() {
+        // the crc table lookup has no selector, every row of its columns must still be checked
+        let message = b"ab";
+        let circuit = Circuit {
+            message: message.iter().map(|byte| Some(*byte)).collect()
+        };
+
+        Tampering::<F, _>::new(17, &circuit, vec![vec![F::from(crc32(message) as u64)]]).assert_sound();
+    }
+
+    pasta_tests!(crc32_test, crc32_wrong_checksum, crc32_tampering);
+}
@@ -1,42 +1,88 @@
-// use halo2_proofs::{
-
-// };
-
 use halo2::{
-    circuit::{Chip, Layouter},
+    circuit::{Chip, Layouter, Region},
     plonk::{Advice, Column, ConstraintSystem, Error, Selector},
     arithmetic::FieldExt,
     poly::Rotation
 };
-use std::{marker::PhantomData};
-use pasta_curves::pallas;
-use crate::word::{AssignedWord, AssignedChunk};
+use std::{convert::TryInto, marker::PhantomData};
+use crate::word::{AssignedWord, AssignedChunk, Endianness, Word};
 
 use crate::gates::{Gate};
 
-pub trait DecomposeInstruction<F: FieldExt> {
+pub trait DecomposeInstruction<F: FieldExt, const BITS: usize, const LIMBS: usize> {
+    /// Decomposes into little-endian byte chunks
     fn decompose(
         &self,
-        layouter: impl Layouter<pallas::Base>,
-        value: AssignedWord,
-    ) -> Result<(AssignedChunk, AssignedChunk, AssignedChunk, AssignedChunk), Error>;
+        layouter: impl Layouter<F>,
+        value: AssignedWord<F, BITS>,
+    ) -> Result<[AssignedChunk<F>; LIMBS], Error>;
+
+    /// Inverse of `decompose`: copies the little-endian chunks into the decompose
+    /// gate and returns the word they make up.
+    fn compose(
+        &self,
+        layouter: impl Layouter<F>,
+        chunks: [AssignedChunk<F>; LIMBS],
+    ) -> Result<AssignedWord<F, BITS>, Error>;
+
+    /// `decompose` with the chunks in the given order
+    fn decompose_with(
+        &self,
+        layouter: impl Layouter<F>,
+        value: AssignedWord<F, BITS>,
+        endianness: Endianness,
+    ) -> Result<[AssignedChunk<F>; LIMBS], Error> {
+        let mut chunks = self.decompose(layouter, value)?;
+        if endianness == Endianness::Big {
+            chunks.reverse();
+        }
+        Ok(chunks)
+    }
+
+    /// `compose` of chunks in the given order
+    fn compose_with(
+        &self,
+        layouter: impl Layouter<F>,
+        mut chunks: [AssignedChunk<F>; LIMBS],
+        endianness: Endianness,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        if endianness == Endianness::Big {
+            chunks.reverse();
+        }
+        self.compose(layouter, chunks)
+    }
+
+    /// Reverses the byte order. The value is decomposed once and the swapped word is composed
+    /// from the same range checked chunks in reverse order, so the bytes are not checked again.
+    fn bswap(
+        &self,
+        layouter: impl Layouter<F>,
+        value: AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error>;
 }
 
+/// Decomposition of a `BITS` wide word into `LIMBS` bytes, `BITS = 8 * LIMBS`.
 #[derive(Clone, Debug)]
-pub struct DecomposeConfig {
+pub struct DecomposeConfig<const BITS: usize, const LIMBS: usize> {
     pub q_decompose: Selector,
+    pub q_bswap: Selector,
     pub advice: [Column<Advice>; 3],
 }
 
+pub type Decompose32Config = DecomposeConfig<32, 4>;
+pub type Decompose64Config = DecomposeConfig<64, 8>;
 
 #[derive(Clone, Debug)]
-pub struct DecomposeChip<F> {
-    config: DecomposeConfig,
+pub struct DecomposeChip<F, const BITS: usize, const LIMBS: usize> {
+    config: DecomposeConfig<BITS, LIMBS>,
     _marker: PhantomData<F>,
 }
 
-impl<F: FieldExt> Chip<F> for DecomposeChip<F> {
-    type Config = DecomposeConfig;
+pub type Decompose32Chip<F> = DecomposeChip<F, 32, 4>;
+pub type Decompose64Chip<F> = DecomposeChip<F, 64, 8>;
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> Chip<F> for DecomposeChip<F, BITS, LIMBS> {
+    type Config = DecomposeConfig<BITS, LIMBS>;
     type Loaded = ();
 
     fn config(&self) -> &Self::Config {
@@ -49,129 +95,335 @@ impl<F: FieldExt> Chip<F> for DecomposeChip<F> {
 }
 
 
-impl<F: FieldExt> DecomposeChip<F> {
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> DecomposeChip<F, BITS, LIMBS> {
     pub fn configure(
         meta: &mut ConstraintSystem<F>,
         advice: [Column<Advice>; 3],
-    ) -> DecomposeConfig {
+    ) -> DecomposeConfig<BITS, LIMBS> {
+        assert_eq!(BITS, 8 * LIMBS);
+
         // we must enable equality so that copy can work
         for advice in advice.iter() {
             meta.enable_equality((*advice).into());
         }
 
         let q_decompose = meta.selector();
+        let q_bswap = meta.selector();
 
         let config = DecomposeConfig {
             q_decompose,
+            q_bswap,
             advice
         };
 
+        // x, x0, x1
+        // x2, x3, x4
+        // ...
         meta.create_gate("constraint decompose", |meta| {
             let q_decompose = meta.query_selector(q_decompose);
-            
+
             let x = meta.query_advice(config.advice[0], Rotation::cur());
 
-            let x0 = meta.query_advice(config.advice[1], Rotation::cur());
-            let x1 = meta.query_advice(config.advice[2], Rotation::cur());
-            let x2 = meta.query_advice(config.advice[0], Rotation::next());
-            let x3 = meta.query_advice(config.advice[1], Rotation::next());
+            let chunks = (0..LIMBS)
+                .map(|i| {
+                    let (column, offset) = Self::chunk_position(&config, i);
+                    meta.query_advice(column, Rotation(offset as i32))
+                })
+                .collect();
+
+            Gate::decompose(q_decompose, x, chunks)
+        });
+
+        // the swapped word follows the chunks of a decompose row
+        meta.create_gate("constraint bswap", |meta| {
+            let q_bswap = meta.query_selector(q_bswap);
+
+            let (column, offset) = Self::chunk_position(&config, LIMBS);
+            let swapped = meta.query_advice(column, Rotation(offset as i32));
 
-            Gate::decompose_32_to_8(
-                q_decompose,
-                x,
-                x0, 
-                x1, 
-                x2, 
-                x3
-            )
+            let chunks = (0..LIMBS)
+                .rev()
+                .map(|i| {
+                    let (column, offset) = Self::chunk_position(&config, i);
+                    meta.query_advice(column, Rotation(offset as i32))
+                })
+                .collect();
 
+            Gate::compose(q_bswap, swapped, chunks)
         });
 
         config
     }
 
-    pub fn construct(config: DecomposeConfig) -> Self {
+    pub fn construct(config: DecomposeConfig<BITS, LIMBS>) -> Self {
         DecomposeChip {
-            config, 
+            config,
             _marker: PhantomData
         }
     }
+
+    /// Cell positions of the chunks, relative to the start of the region
+    fn chunk_position(config: &DecomposeConfig<BITS, LIMBS>, i: usize) -> (Column<Advice>, usize) {
+        let position = i + 1;
+        (config.advice[position % 3], position / 3)
+    }
+
+    /// Copies `value` into the first cell of a decompose row and assigns its chunks
+    fn assign_chunks(
+        &self,
+        region: &mut Region<'_, F>,
+        value: &AssignedWord<F, BITS>,
+    ) -> Result<[AssignedChunk<F>; LIMBS], Error> {
+        let config = self.config();
+
+        let value = value.copy(|| "copy value", region, config.advice[0], 0)?;
+        let decomposed = value.value_word().map(|word| word.decompose::<8, LIMBS>());
+
+        let chunks = (0..LIMBS)
+            .map(|i| {
+                let (column, offset) = Self::chunk_position(config, i);
+                let assigned = region.assign_advice(
+                    || format!("assign x{}", i),
+                    column,
+                    offset,
+                    || decomposed.map(|decomposed| decomposed[i]).ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedChunk::new(assigned))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(chunks.try_into().unwrap())
+    }
 }
 
-impl<F: FieldExt> DecomposeInstruction<F> for DecomposeChip<F> {
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> DecomposeInstruction<F, BITS, LIMBS> for DecomposeChip<F, BITS, LIMBS> {
     fn decompose(
-        &self, 
-        mut layouter: impl Layouter<pallas::Base>,
-        value: AssignedWord
-    ) -> Result<(AssignedChunk, AssignedChunk, AssignedChunk, AssignedChunk), Error> {
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: AssignedWord<F, BITS>
+    ) -> Result<[AssignedChunk<F>; LIMBS], Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "decompose",
+            |mut region| {
+                config.q_decompose.enable(&mut region, 0)?;
+                self.assign_chunks(&mut region, &value)
+            }
+        )
+    }
+
+    fn compose(
+        &self,
+        mut layouter: impl Layouter<F>,
+        chunks: [AssignedChunk<F>; LIMBS],
+    ) -> Result<AssignedWord<F, BITS>, Error> {
         let config = self.config();
 
         layouter.assign_region(
-            || "decompose", 
+            || "compose",
             |mut region| {
-                let mut row_offset = 0;
                 config.q_decompose.enable(&mut region, 0)?;
 
-                let value = {
-                    let assigned = region.assign_advice(
-                        || "witness right",
-                        config.advice[0],
-                        row_offset,
-                        || value.value_word().ok_or(Error::Synthesis),
-                    )?;
-                    AssignedWord::new(assigned)
-                };
-
-                let decomposed = value.value_word().map(|word| word.decompose_4());
-
-                let x0 = {
-                    let assigned = region.assign_advice(
-                        || "assign x0",
-                        config.advice[1],
-                        row_offset,
-                        || decomposed.map(|decomposed| decomposed[0]).ok_or(Error::Synthesis),
-                    )?;
-
-                    AssignedChunk::new(assigned)
-                };
-
-                let x1 = {
-                    let assigned = region.assign_advice(
-                        || "assign x1",
-                        config.advice[2],
-                        row_offset,
-                        || decomposed.map(|decomposed| decomposed[1]).ok_or(Error::Synthesis),
-                    )?;
-
-                    AssignedChunk::new(assigned)
-                };
-
-                row_offset += 1;
-                let x2 = {
-                    let assigned = region.assign_advice(
-                        || "assign x2",
-                        config.advice[0],
-                        row_offset,
-                        || decomposed.map(|decomposed| decomposed[2]).ok_or(Error::Synthesis),
-
-                    )?;
-
-                    AssignedChunk::new(assigned)
-                };
-
-                let x3 = {
-                    let assigned = region.assign_advice(
-                        || "assign x3",
-                        config.advice[1],
-                        row_offset,
-                        || decomposed.map(|decomposed| decomposed[3]).ok_or(Error::Synthesis),
-                    )?;
-
-                    AssignedChunk::new(assigned)
-                };
-
-                Ok((x0, x1, x2, x3))
-            } 
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let (column, offset) = Self::chunk_position(config, i);
+                    chunk.copy(|| format!("copy x{}", i), &mut region, column, offset)?;
+                }
+
+                let composed = chunks
+                    .iter()
+                    .map(|chunk| chunk.value_word())
+                    .collect::<Option<Vec<_>>>()
+                    .map(|chunks| Word::<BITS>::compose::<8, LIMBS>(chunks.try_into().unwrap()));
+
+                let assigned = region.assign_advice(
+                    || "assign composed",
+                    config.advice[0],
+                    0,
+                    || composed.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedWord::new(assigned))
+            }
+        )
+    }
+
+    fn bswap(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+
+        layouter.assign_region(
+            || "bswap",
+            |mut region| {
+                config.q_decompose.enable(&mut region, 0)?;
+                config.q_bswap.enable(&mut region, 0)?;
+
+                let chunks = self.assign_chunks(&mut region, &value)?;
+
+                let swapped = chunks
+                    .iter()
+                    .rev()
+                    .map(|chunk| chunk.value_word())
+                    .collect::<Option<Vec<_>>>()
+                    .map(|chunks| Word::<BITS>::compose::<8, LIMBS>(chunks.try_into().unwrap()));
+
+                let (column, offset) = Self::chunk_position(config, LIMBS);
+                let assigned = region.assign_advice(
+                    || "assign swapped",
+                    column,
+                    offset,
+                    || swapped.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedWord::new(assigned))
+            }
+        )
+    }
+}
+
+/// Splits a `BITS` wide word into two `HALF` wide words and back, the halves
+/// are range checked with the `HALF_LIMBS` byte decomposition.
+#[derive(Clone, Debug)]
+pub struct JoinConfig<const BITS: usize, const HALF: usize, const HALF_LIMBS: usize> {
+    pub q_join: Selector,
+    pub advice: [Column<Advice>; 3],
+    pub decompose_config: DecomposeConfig<HALF, HALF_LIMBS>,
+}
+
+pub type Join64Config = JoinConfig<64, 32, 4>;
+
+#[derive(Clone, Debug)]
+pub struct JoinChip<F, const BITS: usize, const HALF: usize, const HALF_LIMBS: usize> {
+    config: JoinConfig<BITS, HALF, HALF_LIMBS>,
+    _marker: PhantomData<F>,
+}
+
+pub type Join64Chip<F> = JoinChip<F, 64, 32, 4>;
+
+impl<F: FieldExt, const BITS: usize, const HALF: usize, const HALF_LIMBS: usize> Chip<F> for JoinChip<F, BITS, HALF, HALF_LIMBS> {
+    type Config = JoinConfig<BITS, HALF, HALF_LIMBS>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const HALF: usize, const HALF_LIMBS: usize> JoinChip<F, BITS, HALF, HALF_LIMBS> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+        decompose_config: DecomposeConfig<HALF, HALF_LIMBS>,
+    ) -> JoinConfig<BITS, HALF, HALF_LIMBS> {
+        assert_eq!(BITS, 2 * HALF);
+
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+
+        let config = JoinConfig {
+            q_join: meta.selector(),
+            advice,
+            decompose_config,
+        };
+
+        meta.create_gate("constraint join", |meta| {
+            let q_join = meta.query_selector(config.q_join);
+
+            let x = meta.query_advice(config.advice[0], Rotation::cur());
+            let lo = meta.query_advice(config.advice[1], Rotation::cur());
+            let hi = meta.query_advice(config.advice[2], Rotation::cur());
+
+            Gate::join(q_join, x, lo, hi, HALF as u32)
+        });
+
+        config
+    }
+
+    pub fn construct(config: JoinConfig<BITS, HALF, HALF_LIMBS>) -> Self {
+        JoinChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    /// Splits into range checked (lo, hi) halves
+    pub fn split(
+        &self,
+        mut layouter: impl Layouter<F>,
+        value: AssignedWord<F, BITS>,
+    ) -> Result<(AssignedWord<F, HALF>, AssignedWord<F, HALF>), Error> {
+        let config = self.config();
+        let split = value.value_word().map(|word| word.decompose::<HALF, 2>());
+
+        let (lo, hi) = layouter.assign_region(
+            || "split",
+            |mut region| {
+                config.q_join.enable(&mut region, 0)?;
+
+                value.copy(|| "copy value", &mut region, config.advice[0], 0)?;
+
+                let lo = region.assign_advice(
+                    || "lo",
+                    config.advice[1],
+                    0,
+                    || split.map(|[lo, _]| lo).ok_or(Error::Synthesis),
+                )?;
+
+                let hi = region.assign_advice(
+                    || "hi",
+                    config.advice[2],
+                    0,
+                    || split.map(|[_, hi]| hi).ok_or(Error::Synthesis),
+                )?;
+
+                Ok((AssignedWord::new(lo), AssignedWord::new(hi)))
+            }
+        )?;
+
+        let decompose_chip = DecomposeChip::<F, HALF, HALF_LIMBS>::construct(config.decompose_config.clone());
+        decompose_chip.decompose(layouter.namespace(|| "range check lo"), lo.clone())?;
+        decompose_chip.decompose(layouter.namespace(|| "range check hi"), hi.clone())?;
+
+        Ok((lo, hi))
+    }
+
+    /// Joins two range checked halves into `lo + hi * 2^HALF`
+    pub fn join(
+        &self,
+        mut layouter: impl Layouter<F>,
+        lo: AssignedWord<F, HALF>,
+        hi: AssignedWord<F, HALF>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+        let joined = lo.value_word().zip(hi.value_word())
+            .map(|(lo, hi)| Word::<BITS>::compose([lo, hi]));
+
+        layouter.assign_region(
+            || "join",
+            |mut region| {
+                config.q_join.enable(&mut region, 0)?;
+
+                lo.copy(|| "copy lo", &mut region, config.advice[1], 0)?;
+                hi.copy(|| "copy hi", &mut region, config.advice[2], 0)?;
+
+                let assigned = region.assign_advice(
+                    || "joined",
+                    config.advice[0],
+                    0,
+                    || joined.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedWord::new(assigned))
+            }
         )
     }
 }
@@ -179,32 +431,34 @@ impl<F: FieldExt> DecomposeInstruction<F> for DecomposeChip<F> {
 #[cfg(test)]
 mod test {
     use halo2::{
+        arithmetic::FieldExt,
         dev::MockProver,
         circuit::{Layouter, SimpleFloorPlanner},
         plonk::{Advice, Instance, Column, ConstraintSystem, Error},
         plonk,
     };
 
-    use pasta_curves::pallas;
-
-    use super::{DecomposeChip, DecomposeConfig, DecomposeInstruction};
+    use super::{
+        Decompose32Chip, Decompose32Config, Decompose64Chip, Decompose64Config,
+        DecomposeInstruction, Join64Chip, Join64Config,
+    };
 
-    use crate::word::{AssignedWord, Word};
+    use crate::tamper::Tampering;
+    use crate::word::{AssignedWord32, AssignedWord64, Endianness, Word32, Word64};
 
     #[derive(Clone, Debug)]
     pub struct Config {
         advice: [Column<Advice>; 3],
         instance: Column<Instance>,
-        decompose_config: DecomposeConfig
+        decompose_config: Decompose32Config
     }
 
-
     #[derive(Debug, Default)]
     pub struct Circuit {
-        a: Option<Word>
+        a: Option<Word32>
     }
 
-    impl plonk::Circuit<pallas::Base> for Circuit {
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
         type Config = Config;
         type FloorPlanner = SimpleFloorPlanner;
 
@@ -212,7 +466,7 @@ mod test {
             Self::default()
         }
 
-        fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
 
             let advice = [
                 meta.advice_column(),
@@ -227,10 +481,10 @@ mod test {
                 meta.enable_equality((*advice).into());
             }
 
-            let decompose_config = DecomposeChip::configure(meta, advice);
+            let decompose_config = Decompose32Chip::configure(meta, advice);
 
             Config {
-                advice, 
+                advice,
                 instance,
                 decompose_config
             }
@@ -239,29 +493,180 @@ mod test {
         fn synthesize(
             &self,
             config: Self::Config,
-            mut layouter: impl Layouter<pallas::Base>,
+            mut layouter: impl Layouter<F>,
         ) -> Result<(), Error> {
             let config = config.clone();
 
-            let a = AssignedWord::assign_word(layouter.namespace(|| "witness value"), config.advice[0], self.a)?;
+            let a = AssignedWord32::assign_word(layouter.namespace(|| "witness value"), config.advice[0], self.a)?;
 
-            let decompose_chip = DecomposeChip::<pallas::Base>::construct(config.decompose_config.clone());
+            let decompose_chip = Decompose32Chip::<F>::construct(config.decompose_config.clone());
             decompose_chip.decompose(layouter.namespace(|| "decompose"), a)?;
 
             Ok({})
         }
     }
 
-    #[test]
-    fn decompose_test() {
+    fn decompose_test<F: FieldExt>() {
         let k = 4;
-    
+
         let circuit = Circuit {
-            a: Some(Word::new(301931321u32))
+            a: Some(Word32::new(301931321))
         };
 
         let public_inputs = vec![];
-        let prover = MockProver::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
+        let prover = MockProver::<F>::run(k, &circuit, vec![public_inputs.clone()]).unwrap();
         assert_eq!(prover.verify(), Ok(()));
     }
+
+    #[derive(Clone, Debug)]
+    pub struct Config64 {
+        advice: [Column<Advice>; 3],
+        instance: Column<Instance>,
+        decompose_64_config: Decompose64Config,
+        join_config: Join64Config,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit64 {
+        a: Option<Word64>
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit64 {
+        type Config = Config64;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let decompose_config = Decompose32Chip::configure(meta, advice);
+            let decompose_64_config = Decompose64Chip::configure(meta, advice);
+            let join_config = Join64Chip::configure(meta, advice, decompose_config);
+
+            Config64 {
+                advice,
+                instance,
+                decompose_64_config,
+                join_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let decompose_chip = Decompose64Chip::<F>::construct(config.decompose_64_config.clone());
+            let join_chip = Join64Chip::<F>::construct(config.join_config.clone());
+
+            let a = AssignedWord64::assign_word(layouter.namespace(|| "witness value"), config.advice[0], self.a)?;
+
+            let chunks = decompose_chip.decompose(layouter.namespace(|| "decompose"), a.clone())?;
+            let composed = decompose_chip.compose(layouter.namespace(|| "compose"), chunks)?;
+
+            let chunks_be = decompose_chip.decompose_with(layouter.namespace(|| "decompose be"), a.clone(), Endianness::Big)?;
+            let composed_be = decompose_chip.compose_with(layouter.namespace(|| "compose be"), chunks_be.clone(), Endianness::Big)?;
+            let swapped = decompose_chip.bswap(layouter.namespace(|| "bswap"), a.clone())?;
+
+            let (lo, hi) = join_chip.split(layouter.namespace(|| "split"), a)?;
+            let joined = join_chip.join(layouter.namespace(|| "join"), lo.clone(), hi.clone())?;
+
+            layouter.constrain_instance(lo.cell(), config.instance, 0)?;
+            layouter.constrain_instance(hi.cell(), config.instance, 1)?;
+            layouter.constrain_instance(composed.cell(), config.instance, 2)?;
+            layouter.constrain_instance(joined.cell(), config.instance, 3)?;
+            layouter.constrain_instance(chunks_be[0].cell(), config.instance, 4)?;
+            layouter.constrain_instance(composed_be.cell(), config.instance, 5)?;
+            layouter.constrain_instance(swapped.cell(), config.instance, 6)?;
+
+            Ok(())
+        }
+    }
+
+    fn public_inputs<F: FieldExt>(value: u64) -> Vec<F> {
+        vec![
+            F::from(value & 0xffffffff),
+            F::from(value >> 32),
+            F::from(value),
+            F::from(value),
+            F::from(value >> 56),
+            F::from(value),
+            F::from(value.swap_bytes()),
+        ]
+    }
+
+    fn decompose_64_test<F: FieldExt>() {
+        let k = 6;
+
+        for value in [0u64, 1, 0x0123456789abcdef, u64::MAX] {
+            let circuit = Circuit64 {
+                a: Some(Word64::new(value))
+            };
+
+            let prover = MockProver::<F>::run(k, &circuit, vec![public_inputs(value)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn split_wrong_halves<F: FieldExt>() {
+        let k = 6;
+        let value = 0x0123456789abcdefu64;
+
+        let circuit = Circuit64 {
+            a: Some(Word64::new(value))
+        };
+
+        let mut public_inputs = public_inputs(value);
+        public_inputs.swap(0, 1);
+
+        let prover = MockProver::<F>::run(k, &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn bswap_wrong_output<F: FieldExt>() {
+        let k = 6;
+        let value = 0x0123456789abcdefu64;
+
+        let circuit = Circuit64 {
+            a: Some(Word64::new(value))
+        };
+
+        let mut public_inputs = public_inputs(value);
+        public_inputs[6] = F::from(value);
+
+        let prover = MockProver::<F>::run(k, &circuit, vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn decompose_tampering<F: FieldExt>() {
+        let circuit = Circuit {
+            a: Some(Word32::new(301931321))
+        };
+        Tampering::<F, _>::new(4, &circuit, vec![vec![]]).assert_sound();
+
+        let value = 0x0123456789abcdefu64;
+        let circuit = Circuit64 {
+            a: Some(Word64::new(value))
+        };
+        Tampering::<F, _>::new(6, &circuit, vec![public_inputs(value)]).assert_sound();
+    }
+
+    pasta_tests!(
+        decompose_test,
+        decompose_64_test,
+        split_wrong_halves,
+        bswap_wrong_output,
+        decompose_tampering,
+    );
 }
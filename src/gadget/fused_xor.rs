@@ -0,0 +1,410 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter, Region},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+use crate::gadget::xor::{TableChip, TableConfig};
+use crate::gates::Gate;
+use crate::word::{AssignedWord, Chunk, Word};
+
+/// Xor of `BITS` wide words in a single region of `LIMBS` lookup rows.
+///
+/// Row `i` holds the i-th byte of x, y and z in the xor table columns, and the
+/// words x, y and z sit in the first three rows of the word column:
+///
+/// | word | x    | y    | z    |
+/// |------|------|------|------|
+/// | x    | x_0  | y_0  | z_0  |
+/// | y    | x_1  | y_1  | z_1  |
+/// | z    | x_2  | y_2  | z_2  |
+/// |      | x_3  | y_3  | z_3  |
+///
+/// The lookups range check every byte, so unlike `DecomposeChip` the gate stays degree 2
+/// and the words are never copied out of the region.
+#[derive(Clone, Debug)]
+pub struct FusedXorConfig<const BITS: usize, const LIMBS: usize> {
+    pub q_fused: Selector,
+    pub word: Column<Advice>,
+    pub table_config: TableConfig,
+}
+
+pub type FusedXor32Config = FusedXorConfig<32, 4>;
+pub type FusedXor64Config = FusedXorConfig<64, 8>;
+
+#[derive(Clone, Debug)]
+pub struct FusedXorChip<F, const BITS: usize, const LIMBS: usize> {
+    config: FusedXorConfig<BITS, LIMBS>,
+    _marker: PhantomData<F>,
+}
+
+pub type FusedXor32Chip<F> = FusedXorChip<F, 32, 4>;
+pub type FusedXor64Chip<F> = FusedXorChip<F, 64, 8>;
+
+/// An input of the region, either witnessed in it or copied from another region
+enum Operand<'a, F: FieldExt, const BITS: usize> {
+    Witness(Option<Word<BITS>>),
+    Copy(&'a AssignedWord<F, BITS>),
+}
+
+impl<F: FieldExt, const BITS: usize> Operand<'_, F, BITS> {
+    fn value(&self) -> Option<Word<BITS>> {
+        match self {
+            Operand::Witness(value) => *value,
+            Operand::Copy(word) => word.value_word(),
+        }
+    }
+
+    fn assign(
+        &self,
+        region: &mut Region<'_, F>,
+        column: Column<Advice>,
+        row: usize,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        match self {
+            Operand::Witness(value) => {
+                let assigned = region.assign_advice(|| format!("word {}", row), column, row, || value.ok_or(Error::Synthesis))?;
+                Ok(AssignedWord::new(assigned))
+            }
+            Operand::Copy(word) => word.copy(|| format!("word {}", row), region, column, row),
+        }
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> Chip<F> for FusedXorChip<F, BITS, LIMBS> {
+    type Config = FusedXorConfig<BITS, LIMBS>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> FusedXorChip<F, BITS, LIMBS> {
+    /// `table_config` is shared with the other users of the xor table, `word` must not
+    /// be one of its input columns
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        word: Column<Advice>,
+        table_config: TableConfig,
+    ) -> FusedXorConfig<BITS, LIMBS> {
+        // the three words need a row each
+        assert!(BITS == 8 * LIMBS && LIMBS >= 3);
+
+        meta.enable_equality(word.into());
+
+        let config = FusedXorConfig {
+            q_fused: meta.selector(),
+            word,
+            table_config,
+        };
+
+        meta.create_gate("fused xor", |meta| {
+            let q_fused = meta.query_selector(config.q_fused);
+
+            let words = [0, 1, 2].map(|row| meta.query_advice(config.word, Rotation(row)));
+
+            let input = &config.table_config.input;
+            let chunks = [input.x, input.y, input.z].map(|column| {
+                (0..LIMBS)
+                    .map(|row| meta.query_advice(column, Rotation(row as i32)))
+                    .collect::<Vec<_>>()
+            });
+
+            Gate::fused_xor(q_fused, words, chunks)
+        });
+
+        config
+    }
+
+    pub fn construct(config: FusedXorConfig<BITS, LIMBS>) -> Self {
+        FusedXorChip {
+            config,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Witnesses x and y and returns `[x, y, x ^ y]`, all three range checked
+    pub fn witness_xor(
+        &self,
+        layouter: impl Layouter<F>,
+        x: Option<Word<BITS>>,
+        y: Option<Word<BITS>>,
+    ) -> Result<[AssignedWord<F, BITS>; 3], Error> {
+        self.layout(layouter, Operand::Witness(x), Operand::Witness(y))
+    }
+
+    /// `x ^ y` with x and y copied in, which also range checks them
+    pub fn xor(
+        &self,
+        layouter: impl Layouter<F>,
+        x: &AssignedWord<F, BITS>,
+        y: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let [_, _, z] = self.layout(layouter, Operand::Copy(x), Operand::Copy(y))?;
+        Ok(z)
+    }
+
+    fn layout(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: Operand<'_, F, BITS>,
+        y: Operand<'_, F, BITS>,
+    ) -> Result<[AssignedWord<F, BITS>; 3], Error> {
+        let config = &self.config;
+        let table_chip = TableChip::<F>::construct(config.table_config.clone());
+
+        let x_chunks = x.value().map(|x| x.decompose::<8, LIMBS>());
+        let y_chunks = y.value().map(|y| y.decompose::<8, LIMBS>());
+        let z = x.value().zip(y.value()).map(|(x, y)| x ^ y);
+
+        layouter.assign_region(
+            || "fused xor",
+            |mut region| {
+                config.q_fused.enable(&mut region, 0)?;
+
+                for row in 0..LIMBS {
+                    let x: Option<Chunk> = x_chunks.map(|chunks| chunks[row]);
+                    let y: Option<Chunk> = y_chunks.map(|chunks| chunks[row]);
+                    table_chip.witness_xor_row(&mut region, row, x, y)?;
+                }
+
+                let x = x.assign(&mut region, config.word, 0)?;
+                let y = y.assign(&mut region, config.word, 1)?;
+                let z = Operand::Witness(z).assign(&mut region, config.word, 2)?;
+
+                Ok([x, y, z])
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use std::convert::TryInto;
+
+    use pasta_curves::pallas;
+
+    use super::{FusedXorChip, FusedXorConfig};
+
+    use crate::cost::{CostReport, RecordingLayouter, RegionCost};
+    use crate::gadget::{
+        bitwise::{Bitwise32Chip, Bitwise32Config, BitwiseInstructions},
+        decompose::Decompose32Chip,
+        xor::TableChip,
+    };
+    use crate::tamper::Tampering;
+    use crate::word::{Word, Word32};
+
+    #[derive(Clone, Debug)]
+    pub struct Config<const BITS: usize, const LIMBS: usize> {
+        instance: Column<Instance>,
+        fused_config: FusedXorConfig<BITS, LIMBS>,
+    }
+
+    /// Exposes `x ^ y` and `(x ^ y) ^ y` of every pair, the latter through the copying `xor`
+    #[derive(Debug, Default)]
+    pub struct Circuit<const BITS: usize, const LIMBS: usize> {
+        pairs: Vec<(Option<Word<BITS>>, Option<Word<BITS>>)>,
+    }
+
+    impl<F: FieldExt, const BITS: usize, const LIMBS: usize> plonk::Circuit<F> for Circuit<BITS, LIMBS> {
+        type Config = Config<BITS, LIMBS>;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                pairs: vec![(None, None); self.pairs.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[0], advice[1], advice[2]);
+            let fused_config = FusedXorChip::configure(meta, advice[3], table_config);
+
+            Config {
+                instance,
+                fused_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.fused_config.table_config.clone(), &mut layouter)?;
+            let chip = FusedXorChip::<F, BITS, LIMBS>::construct(config.fused_config.clone());
+
+            for (i, (x, y)) in self.pairs.iter().enumerate() {
+                let [_, y, z] = chip.witness_xor(layouter.namespace(|| format!("xor {}", i)), *x, *y)?;
+                let x = chip.xor(layouter.namespace(|| format!("xor back {}", i)), &z, &y)?;
+
+                layouter.constrain_instance(z.cell(), config.instance, 2 * i)?;
+                layouter.constrain_instance(x.cell(), config.instance, 2 * i + 1)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// `Circuit` laid out by `BitwiseChip`: both words loaded, decomposed and looked up separately
+    #[derive(Debug, Default)]
+    pub struct BitwiseCircuit {
+        pairs: Vec<(Option<Word32>, Option<Word32>)>,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for BitwiseCircuit {
+        type Config = (Column<Instance>, Bitwise32Config);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            BitwiseCircuit {
+                pairs: vec![(None, None); self.pairs.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: Vec<Column<Advice>> = (0..6).map(|_| meta.advice_column()).collect();
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_config = Bitwise32Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_config,
+                table_config
+            );
+
+            (instance, bitwise_config)
+        }
+
+        fn synthesize(
+            &self,
+            (instance, config): Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.table_config.clone(), &mut layouter)?;
+            let chip = Bitwise32Chip::<F>::construct(config);
+
+            for (i, (x, y)) in self.pairs.iter().enumerate() {
+                let x = chip.load_word(layouter.namespace(|| format!("load x {}", i)), *x)?;
+                let y = chip.load_word(layouter.namespace(|| format!("load y {}", i)), *y)?;
+                let z = chip.xor(layouter.namespace(|| format!("xor {}", i)), &x, &y)?;
+                let x = chip.xor(layouter.namespace(|| format!("xor back {}", i)), &z, &y)?;
+
+                layouter.constrain_instance(z.cell(), instance, 2 * i)?;
+                layouter.constrain_instance(x.cell(), instance, 2 * i + 1)?;
+            }
+            Ok(())
+        }
+    }
+
+    fn circuit<const BITS: usize, const LIMBS: usize>(pairs: &[(u64, u64)]) -> Circuit<BITS, LIMBS> {
+        Circuit {
+            pairs: pairs
+                .iter()
+                .map(|(x, y)| (Some(Word::new(*x)), Some(Word::new(*y))))
+                .collect(),
+        }
+    }
+
+    fn expected<F: FieldExt>(pairs: &[(u64, u64)]) -> Vec<F> {
+        pairs
+            .iter()
+            .flat_map(|(x, y)| [x ^ y, *x])
+            .map(F::from)
+            .collect()
+    }
+
+    fn fused_xor_test<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        let pairs: Vec<_> = (0..16).map(|_| (rng.gen::<u32>() as u64, rng.gen::<u32>() as u64)).collect();
+        let prover = MockProver::<F>::run(k, &circuit::<32, 4>(&pairs), vec![expected(&pairs)]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let pairs: Vec<_> = (0..16).map(|_| (rng.gen(), rng.gen())).collect();
+        let prover = MockProver::<F>::run(k, &circuit::<64, 8>(&pairs), vec![expected(&pairs)]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    fn fused_xor_wrong_output<F: FieldExt>() {
+        let k = 17;
+        let pairs = [(0xdeadbeef, 0x01234567)];
+
+        let mut wrong = expected(&pairs);
+        wrong[0] = F::from(0xdeadbeef ^ 0x01234567 ^ 1);
+
+        let prover = MockProver::<F>::run(k, &circuit::<32, 4>(&pairs), vec![wrong]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn fused_xor_tampering<F: FieldExt>() {
+        let k = 17;
+
+        let pairs = [(0xdeadbeef, 0x01234567)];
+        Tampering::<F, _>::new(k, &circuit::<32, 4>(&pairs), vec![expected(&pairs)]).assert_sound();
+
+        let pairs = [(0x0123456789abcdef, u64::MAX)];
+        Tampering::<F, _>::new(k, &circuit::<64, 8>(&pairs), vec![expected(&pairs)]).assert_sound();
+    }
+
+    #[test]
+    fn fused_xor_cost() {
+        // the lookup argument sets the degree, not the degree 2 gate
+        let report = CostReport::measure(&Circuit::<32, 4>::default()).unwrap();
+        assert_eq!(report.max_degree, 4);
+
+        let circuit = Circuit::<32, 4> { pairs: vec![(None, None); 8] };
+        let report = CostReport::measure(&circuit).unwrap();
+        assert_eq!(report.regions["fused xor"], RegionCost { count: 16, rows: 64 });
+        assert_eq!(report.chips["FusedXorChip"], RegionCost { count: 16, rows: 64 });
+
+        // only the copying xor and the instance cells need copies, two each per pair
+        let (_, layouter) = RecordingLayouter::<pallas::Base>::record(&circuit).unwrap();
+        assert_eq!(layouter.copies(), 32);
+    }
+
+    #[test]
+    fn fused_xor_layout() {
+        // rows and copies of the same xors laid out by both chips, the xor table aside
+        let pairs = vec![(None, None); 16];
+        let (_, fused) = RecordingLayouter::<pallas::Base>::record(&Circuit::<32, 4> { pairs: pairs.clone() }).unwrap();
+        let (_, bitwise) = RecordingLayouter::<pallas::Base>::record(&BitwiseCircuit { pairs }).unwrap();
+
+        assert!(fused.region_rows() < bitwise.region_rows(), "{} rows, {} for BitwiseChip", fused.region_rows(), bitwise.region_rows());
+        assert!(fused.copies() < bitwise.copies(), "{} copies, {} for BitwiseChip", fused.copies(), bitwise.copies());
+    }
+
+    pasta_tests!(fused_xor_test, fused_xor_wrong_output, fused_xor_tampering);
+}
@@ -0,0 +1,288 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{ConstraintSystem, Error},
+};
+use std::marker::PhantomData;
+
+use crate::gadget::{
+    sha256::{Sha256Chip, Sha256Config, DIGEST_SIZE},
+    xor::TableChip,
+};
+use crate::word::{AssignedChunk, AssignedWord32, Chunk};
+
+/// SHA-256 block size in bytes
+const BLOCK_BYTES: usize = 64;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+#[derive(Clone, Debug)]
+pub struct HmacSha256Config {
+    pub sha256_config: Sha256Config,
+}
+
+/// HMAC-SHA256 (RFC 2104) of a private key and message
+#[derive(Clone, Debug)]
+pub struct HmacSha256Chip<F> {
+    config: HmacSha256Config,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for HmacSha256Chip<F> {
+    type Config = HmacSha256Config;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> HmacSha256Chip<F> {
+    pub fn configure(
+        _meta: &mut ConstraintSystem<F>,
+        sha256_config: Sha256Config,
+    ) -> HmacSha256Config {
+        HmacSha256Config {
+            sha256_config
+        }
+    }
+
+    pub fn construct(config: HmacSha256Config) -> Self {
+        HmacSha256Chip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: HmacSha256Config,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.sha256_config.bitwise_config.table_config, layouter)
+    }
+
+    fn sha256_chip(&self) -> Sha256Chip<F> {
+        Sha256Chip::construct(self.config.sha256_config.clone())
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.sha256_config.bitwise_config.table_config.clone())
+    }
+
+    /// Witnesses key or message bytes, range checked through the xor table
+    pub fn load_bytes(
+        &self,
+        layouter: impl Layouter<F>,
+        bytes: &[Option<u8>],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        self.table_chip().load_chunks(layouter, bytes)
+    }
+
+    /// HMAC of range checked `key` and `message` bytes
+    pub fn mac(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: &[AssignedChunk<F>],
+        message: &[AssignedChunk<F>],
+    ) -> Result<[AssignedWord32<F>; DIGEST_SIZE], Error> {
+        let sha256_chip = self.sha256_chip();
+
+        // keys longer than a block are replaced by their hash
+        let key = if key.len() > BLOCK_BYTES {
+            let digest = sha256_chip.hash_bytes(layouter.namespace(|| "hash key"), key)?;
+            sha256_chip.digest_bytes(layouter.namespace(|| "key bytes"), &digest)?
+        } else {
+            key.to_vec()
+        };
+
+        let inner_key = self.xor_pad(layouter.namespace(|| "ipad"), &key, IPAD)?;
+        let outer_key = self.xor_pad(layouter.namespace(|| "opad"), &key, OPAD)?;
+
+        let inner = sha256_chip.hash_bytes(layouter.namespace(|| "inner hash"), &[&inner_key, message].concat())?;
+        let inner = sha256_chip.digest_bytes(layouter.namespace(|| "inner bytes"), &inner)?;
+
+        sha256_chip.hash_bytes(layouter.namespace(|| "outer hash"), &[outer_key, inner].concat())
+    }
+
+    /// Xors the key, zero padded to a block, with `pad`
+    fn xor_pad(
+        &self,
+        mut layouter: impl Layouter<F>,
+        key: &[AssignedChunk<F>],
+        pad: u8,
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        let table_chip = self.table_chip();
+
+        let mut padded = layouter.assign_region(
+            || "xor pad",
+            |mut region| {
+                key
+                    .iter()
+                    .enumerate()
+                    .map(|(row, byte)| table_chip.xor_constant_row(&mut region, row, byte, Chunk::new(pad as u64)))
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?;
+
+        // the zero padding xors to the pad itself
+        let tail = vec![pad; BLOCK_BYTES - key.len()];
+        padded.extend(self.sha256_chip().constant_bytes(layouter.namespace(|| "pad tail"), &tail)?);
+
+        Ok(padded)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use std::convert::TryInto;
+
+    use super::{HmacSha256Chip, HmacSha256Config};
+
+    use crate::gadget::{
+        bitwise::Bitwise32Chip,
+        decompose::Decompose32Chip,
+        sha256::{Sha256Chip, SHA256},
+        xor::TableChip,
+    };
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        hmac_config: HmacSha256Config
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        key: Vec<Option<u8>>,
+        message: Vec<Option<u8>>,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                key: vec![None; self.key.len()],
+                message: vec![None; self.message.len()],
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_config = Bitwise32Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_config,
+                table_config
+            );
+            let sha256_config = Sha256Chip::configure(meta, bitwise_config, &SHA256);
+            let hmac_config = HmacSha256Chip::configure(meta, sha256_config);
+
+            Config {
+                instance,
+                hmac_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            HmacSha256Chip::<F>::load(config.hmac_config.clone(), &mut layouter)?;
+            let chip = HmacSha256Chip::<F>::construct(config.hmac_config.clone());
+
+            let key = chip.load_bytes(layouter.namespace(|| "key"), &self.key)?;
+            let message = chip.load_bytes(layouter.namespace(|| "message"), &self.message)?;
+
+            let mac = chip.mac(layouter.namespace(|| "hmac"), &key, &message)?;
+
+            for (row, word) in mac.iter().enumerate() {
+                layouter.constrain_instance(word.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn circuit(key: &[u8], message: &[u8]) -> Circuit {
+        Circuit {
+            key: key.iter().copied().map(Some).collect(),
+            message: message.iter().copied().map(Some).collect(),
+        }
+    }
+
+    fn mac_words<F: FieldExt>(hex: &str) -> Vec<F> {
+        (0..hex.len())
+            .step_by(8)
+            .map(|i| F::from(u32::from_str_radix(&hex[i..i + 8], 16).unwrap() as u64))
+            .collect()
+    }
+
+    fn hmac_sha256_rfc4231<F: FieldExt>() {
+        let k = 17;
+
+        let range: Vec<u8> = (1..=25).collect();
+        let vectors: [(&[u8], &[u8], &str); 4] = [
+            (&[0x0b; 20], b"Hi There", "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"),
+            (b"Jefe", b"what do ya want for nothing?", "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"),
+            (&[0xaa; 20], &[0xdd; 50], "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe"),
+            (&range, &[0xcd; 50], "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b"),
+        ];
+
+        for (key, message, expected) in vectors {
+            let prover = MockProver::<F>::run(k, &circuit(key, message), vec![mac_words(expected)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn hmac_sha256_long_key<F: FieldExt>() {
+        let k = 18;
+
+        // RFC 4231 test case 6, the key is hashed first
+        let key = [0xaa; 131];
+        let message = b"Test Using Larger Than Block-Size Key - Hash Key First";
+        let expected = "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54";
+
+        let prover = MockProver::<F>::run(k, &circuit(&key, message), vec![mac_words(expected)]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    fn hmac_sha256_wrong_key<F: FieldExt>() {
+        let k = 17;
+
+        let expected = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+        let prover = MockProver::<F>::run(k, &circuit(b"Jeff", b"what do ya want for nothing?"), vec![mac_words(expected)]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(hmac_sha256_rfc4231, hmac_sha256_long_key, hmac_sha256_wrong_key);
+}
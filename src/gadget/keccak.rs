@@ -0,0 +1,582 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{ConstraintSystem, Error},
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::{
+    bitwise::{Bitwise64Chip, Bitwise64Config, BitwiseInstructions},
+    chi::ChiTableChip,
+    decompose::{Decompose64Chip, DecomposeInstruction},
+    xor::{TableChip, TableConfig},
+};
+use crate::word::{AssignedChunk, Chunk};
+
+/// A 64-bit lane as little-endian byte chunks
+pub type Lane<F> = [AssignedChunk<F>; 8];
+
+/// The 25 lanes of the state, lane (x, y) is at index x + 5 * y
+pub type State<F> = [Lane<F>; 25];
+
+/// Keccak-256 rate in lanes (1088 bits)
+pub const RATE: usize = 17;
+pub const DIGEST_BYTES: usize = 32;
+
+/// Domain separation byte of the original Keccak padding, as used by Ethereum
+pub const KECCAK_DOMAIN: u8 = 0x01;
+/// Domain separation byte of FIPS-202 SHA3
+pub const SHA3_DOMAIN: u8 = 0x06;
+
+const ROUNDS: usize = 24;
+
+const ROUND_CONSTANTS: [u64; ROUNDS] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+/// ρ offsets, indexed by x + 5 * y
+const ROTATIONS: [u32; 25] = [
+    0, 1, 62, 28, 27,
+    36, 44, 6, 55, 20,
+    3, 10, 43, 25, 39,
+    41, 45, 15, 21, 8,
+    18, 2, 61, 56, 14,
+];
+
+/// Padding appended to a message of `len` bytes (pad10*1 after the domain bits)
+pub fn padding(len: usize, domain: u8) -> Vec<u8> {
+    let rate_bytes = RATE * 8;
+    let mut padding = vec![0u8; rate_bytes - len % rate_bytes];
+    padding[0] |= domain;
+    *padding.last_mut().unwrap() |= 0x80;
+    padding
+}
+
+#[derive(Clone, Debug)]
+pub struct KeccakConfig {
+    pub bitwise_64_config: Bitwise64Config,
+    pub chi_table_config: TableConfig,
+}
+
+#[derive(Clone, Debug)]
+pub struct KeccakChip<F> {
+    config: KeccakConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for KeccakChip<F> {
+    type Config = KeccakConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> KeccakChip<F> {
+    pub fn configure(
+        _meta: &mut ConstraintSystem<F>,
+        bitwise_64_config: Bitwise64Config,
+        chi_table_config: TableConfig,
+    ) -> KeccakConfig {
+        KeccakConfig {
+            bitwise_64_config,
+            chi_table_config,
+        }
+    }
+
+    pub fn construct(config: KeccakConfig) -> Self {
+        KeccakChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    /// Loads both the xor and the chi table
+    pub fn load(
+        config: KeccakConfig,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.bitwise_64_config.table_config.clone(), layouter)?;
+        ChiTableChip::load(config.chi_table_config, layouter)
+    }
+
+    fn bitwise_64_chip(&self) -> Bitwise64Chip<F> {
+        Bitwise64Chip::construct(self.config.bitwise_64_config.clone())
+    }
+
+    fn decompose_64_chip(&self) -> Decompose64Chip<F> {
+        Decompose64Chip::construct(self.config.bitwise_64_config.decompose_config.clone())
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.bitwise_64_config.table_config.clone())
+    }
+
+    fn chi_table_chip(&self) -> ChiTableChip<F> {
+        ChiTableChip::construct(self.config.chi_table_config.clone())
+    }
+
+    /// Witnesses message bytes, range checked through the xor table
+    pub fn load_message(
+        &self,
+        layouter: impl Layouter<F>,
+        message: &[Option<u8>],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        self.table_chip().load_chunks(layouter, message)
+    }
+
+    fn constant_chunks(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[u8],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        let config = &self.config.bitwise_64_config;
+
+        layouter.assign_region(
+            || "constant chunks",
+            |mut region| {
+                bytes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, byte)| {
+                        let assigned = region.assign_advice_from_constant(
+                            || format!("constant {}", i),
+                            config.advice[i % 3],
+                            i / 3,
+                            Chunk::new(*byte as u64),
+                        )?;
+
+                        Ok(AssignedChunk::new(assigned))
+                    })
+                    .collect()
+            },
+        )
+    }
+
+    /// Keccak-256 as used by Ethereum
+    pub fn keccak256(
+        &self,
+        layouter: impl Layouter<F>,
+        message: &[AssignedChunk<F>],
+    ) -> Result<[AssignedChunk<F>; DIGEST_BYTES], Error> {
+        self.hash(layouter, message, KECCAK_DOMAIN)
+    }
+
+    /// FIPS-202 SHA3-256
+    pub fn sha3_256(
+        &self,
+        layouter: impl Layouter<F>,
+        message: &[AssignedChunk<F>],
+    ) -> Result<[AssignedChunk<F>; DIGEST_BYTES], Error> {
+        self.hash(layouter, message, SHA3_DOMAIN)
+    }
+
+    /// Pads `message` with constant chunks, absorbs it and squeezes 256 bits
+    pub fn hash(
+        &self,
+        mut layouter: impl Layouter<F>,
+        message: &[AssignedChunk<F>],
+        domain: u8,
+    ) -> Result<[AssignedChunk<F>; DIGEST_BYTES], Error> {
+        let padding = self.constant_chunks(
+            layouter.namespace(|| "padding"),
+            &padding(message.len(), domain),
+        )?;
+
+        let padded: Vec<AssignedChunk<F>> = message.iter().cloned().chain(padding).collect();
+
+        let mut state = None;
+        for (i, block) in padded.chunks(RATE * 8).enumerate() {
+            state = Some(self.absorb(layouter.namespace(|| format!("absorb block {}", i)), state, block)?);
+        }
+
+        Ok(self.squeeze(&state.unwrap()))
+    }
+
+    /// XORs a rate sized block into the state and applies the permutation.
+    /// A `None` state stands for the all zero initial state.
+    pub fn absorb(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: Option<State<F>>,
+        block: &[AssignedChunk<F>],
+    ) -> Result<State<F>, Error> {
+        assert_eq!(block.len(), RATE * 8);
+
+        let block_lanes: Vec<Lane<F>> = block
+            .chunks(8)
+            .map(|lane| lane.to_vec().try_into().unwrap())
+            .collect();
+
+        let state: Vec<Lane<F>> = match state {
+            Some(state) => state
+                .iter()
+                .enumerate()
+                .map(|(i, lane)| {
+                    if i < RATE {
+                        self.xor_lanes(layouter.namespace(|| format!("absorb lane {}", i)), lane, &block_lanes[i])
+                    } else {
+                        Ok(lane.clone())
+                    }
+                })
+                .collect::<Result<_, Error>>()?,
+            None => {
+                let zeros = self.constant_chunks(layouter.namespace(|| "capacity"), &[0u8; (25 - RATE) * 8])?;
+                let capacity = zeros.chunks(8).map(|lane| lane.to_vec().try_into().unwrap());
+
+                block_lanes.into_iter().chain(capacity).collect()
+            }
+        };
+
+        self.permute(layouter.namespace(|| "keccak-f"), state.try_into().unwrap())
+    }
+
+    /// The first 256 bits of the state
+    pub fn squeeze(&self, state: &State<F>) -> [AssignedChunk<F>; DIGEST_BYTES] {
+        let bytes: Vec<AssignedChunk<F>> = state[..DIGEST_BYTES / 8]
+            .iter()
+            .flat_map(|lane| lane.iter().cloned())
+            .collect();
+
+        bytes.try_into().unwrap()
+    }
+
+    /// Keccak-f[1600]
+    pub fn permute(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: State<F>,
+    ) -> Result<State<F>, Error> {
+        let mut state = state;
+
+        for round in 0..ROUNDS {
+            state = self.round(layouter.namespace(|| format!("round {}", round)), state, round)?;
+        }
+
+        Ok(state)
+    }
+
+    fn round(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: State<F>,
+        round: usize,
+    ) -> Result<State<F>, Error> {
+        // θ
+        let c = (0..5)
+            .map(|x| {
+                let mut c = a[x].clone();
+                for y in 1..5 {
+                    c = self.xor_lanes(layouter.namespace(|| format!("theta c {} {}", x, y)), &c, &a[x + 5 * y])?;
+                }
+                Ok(c)
+            })
+            .collect::<Result<Vec<Lane<F>>, Error>>()?;
+
+        let d = (0..5)
+            .map(|x| {
+                let rotated = self.rotate_lane(layouter.namespace(|| format!("theta rot {}", x)), &c[(x + 1) % 5], 1)?;
+                self.xor_lanes(layouter.namespace(|| format!("theta d {}", x)), &c[(x + 4) % 5], &rotated)
+            })
+            .collect::<Result<Vec<Lane<F>>, Error>>()?;
+
+        let a = a
+            .iter()
+            .enumerate()
+            .map(|(i, lane)| self.xor_lanes(layouter.namespace(|| format!("theta {}", i)), lane, &d[i % 5]))
+            .collect::<Result<Vec<Lane<F>>, Error>>()?;
+
+        // ρ and π
+        let mut b: Vec<Option<Lane<F>>> = vec![None; 25];
+        for x in 0..5 {
+            for y in 0..5 {
+                let rotated = self.rotate_lane(
+                    layouter.namespace(|| format!("rho {} {}", x, y)),
+                    &a[x + 5 * y],
+                    ROTATIONS[x + 5 * y],
+                )?;
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = Some(rotated);
+            }
+        }
+        let b: Vec<Lane<F>> = b.into_iter().map(|lane| lane.unwrap()).collect();
+
+        // χ
+        let mut a = (0..25)
+            .map(|i| {
+                let (x, y) = (i % 5, i / 5);
+                self.chi_lanes(
+                    layouter.namespace(|| format!("chi {}", i)),
+                    &b[i],
+                    &b[(x + 1) % 5 + 5 * y],
+                    &b[(x + 2) % 5 + 5 * y],
+                )
+            })
+            .collect::<Result<Vec<Lane<F>>, Error>>()?;
+
+        // ι
+        a[0] = self.xor_constant_lane(layouter.namespace(|| "iota"), &a[0], ROUND_CONSTANTS[round])?;
+
+        Ok(a.try_into().unwrap())
+    }
+
+    fn xor_lanes(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &Lane<F>,
+        b: &Lane<F>,
+    ) -> Result<Lane<F>, Error> {
+        let table_chip = self.table_chip();
+
+        layouter.assign_region(
+            || "xor lanes",
+            |mut region| {
+                let z = a
+                    .iter()
+                    .zip(b.iter())
+                    .enumerate()
+                    .map(|(row, (a, b))| table_chip.xor_row(&mut region, row, a, b))
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                Ok(z.try_into().unwrap())
+            },
+        )
+    }
+
+    fn xor_constant_lane(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &Lane<F>,
+        constant: u64,
+    ) -> Result<Lane<F>, Error> {
+        let table_chip = self.table_chip();
+
+        layouter.assign_region(
+            || "xor constant lane",
+            |mut region| {
+                let mut row = 0;
+                let mut z = a.clone();
+
+                for (i, byte) in constant.to_le_bytes().iter().enumerate() {
+                    // xor with zero is the identity, no lookup needed
+                    if *byte != 0 {
+                        z[i] = table_chip.xor_constant_row(&mut region, row, &a[i], Chunk::new(*byte as u64))?;
+                        row += 1;
+                    }
+                }
+
+                Ok(z)
+            },
+        )
+    }
+
+    /// a ^ (!b & c) bytewise
+    fn chi_lanes(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &Lane<F>,
+        b: &Lane<F>,
+        c: &Lane<F>,
+    ) -> Result<Lane<F>, Error> {
+        let table_chip = self.table_chip();
+        let chi_table_chip = self.chi_table_chip();
+
+        layouter.assign_region(
+            || "chi lanes",
+            |mut region| {
+                let z = (0..8)
+                    .map(|row| {
+                        let t = chi_table_chip.chi_row(&mut region, row, &b[row], &c[row])?;
+                        table_chip.xor_row(&mut region, row, &a[row], &t)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                Ok(z.try_into().unwrap())
+            },
+        )
+    }
+
+    /// Byte aligned rotations only permute the chunks, everything else goes through
+    /// a 64-bit rotation of the composed lane.
+    fn rotate_lane(
+        &self,
+        mut layouter: impl Layouter<F>,
+        lane: &Lane<F>,
+        n: u32,
+    ) -> Result<Lane<F>, Error> {
+        if n.is_multiple_of(8) {
+            let q = (n / 8) as usize;
+            let rotated: Vec<AssignedChunk<F>> = (0..8).map(|i| lane[(i + 8 - q) % 8].clone()).collect();
+            return Ok(rotated.try_into().unwrap());
+        }
+
+        let decompose_64_chip = self.decompose_64_chip();
+
+        let word = decompose_64_chip.compose(layouter.namespace(|| "compose"), lane.clone())?;
+        let rotated = self.bitwise_64_chip().rotl(layouter.namespace(|| "rotl"), &word, n)?;
+        decompose_64_chip.decompose(layouter.namespace(|| "decompose"), rotated)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use std::convert::TryInto;
+
+    use super::{KeccakChip, KeccakConfig, KECCAK_DOMAIN, SHA3_DOMAIN};
+
+    use crate::gadget::{
+        bitwise::Bitwise64Chip,
+        chi::ChiTableChip,
+        decompose::Decompose64Chip,
+        xor::TableChip,
+    };
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        keccak_config: KeccakConfig
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        message: Vec<Option<u8>>,
+        domain: u8,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                message: vec![None; self.message.len()],
+                domain: self.domain,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 9] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let chi_table_config = ChiTableChip::configure(meta, advice[6], advice[7], advice[8]);
+            let decompose_64_config = Decompose64Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_64_config = Bitwise64Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_64_config,
+                table_config
+            );
+            let keccak_config = KeccakChip::configure(meta, bitwise_64_config, chi_table_config);
+
+            Config {
+                instance,
+                keccak_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            KeccakChip::load(config.keccak_config.clone(), &mut layouter)?;
+            let chip = KeccakChip::<F>::construct(config.keccak_config.clone());
+
+            let message = chip.load_message(layouter.namespace(|| "message"), &self.message)?;
+            let digest = chip.hash(layouter.namespace(|| "hash"), &message, self.domain)?;
+
+            for (row, byte) in digest.iter().enumerate() {
+                layouter.constrain_instance(byte.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn digest_bytes<F: FieldExt>(hex: &str) -> Vec<F> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| F::from(u8::from_str_radix(&hex[i..i + 2], 16).unwrap() as u64))
+            .collect()
+    }
+
+    fn run<F: FieldExt>(message: &[u8], domain: u8, expected: &str) -> MockProver<F> {
+        let circuit = Circuit {
+            message: message.iter().map(|byte| Some(*byte)).collect(),
+            domain,
+        };
+
+        MockProver::<F>::run(17, &circuit, vec![digest_bytes(expected)]).unwrap()
+    }
+
+    fn keccak256_known_answers<F: FieldExt>() {
+        let vectors: [(&[u8], &str); 2] = [
+            (b"", "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+            (b"abc", "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+        ];
+
+        for (message, expected) in vectors {
+            assert_eq!(run::<F>(message, KECCAK_DOMAIN, expected).verify(), Ok(()));
+        }
+    }
+
+    fn sha3_256_known_answers<F: FieldExt>() {
+        // 135 bytes leave a single padding byte, 200 bytes span two blocks
+        let one_padding_byte = [b'a'; 135];
+        let two_blocks = [b'a'; 200];
+
+        let vectors: [(&[u8], &str); 4] = [
+            (b"", "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"),
+            (b"abc", "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"),
+            (&one_padding_byte, "8094bb53c44cfb1e67b7c30447f9a1c33696d2463ecc1d9c92538913392843c9"),
+            (&two_blocks, "cce34485baf2bf2aca99b94833892a4f52896d3d153f7b840cc4f9fe695f1387"),
+        ];
+
+        for (message, expected) in vectors {
+            assert_eq!(run::<F>(message, SHA3_DOMAIN, expected).verify(), Ok(()));
+        }
+    }
+
+    fn keccak256_wrong_digest<F: FieldExt>() {
+        let prover = run::<F>(
+            b"abd",
+            KECCAK_DOMAIN,
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(keccak256_known_answers, sha3_256_known_answers, keccak256_wrong_digest);
+}
@@ -0,0 +1,83 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::Layouter,
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector, TableColumn},
+    poly::Rotation,
+};
+
+/// A lookup table of three columns, `row(i)` is its `i`-th row
+#[derive(Clone, Copy, Debug)]
+pub struct LookupTable {
+    pub name: &'static str,
+    pub len: usize,
+    pub row: fn(usize) -> [u64; 3],
+}
+
+impl LookupTable {
+    /// Looks `input` up in three new table columns. Without `q_lookup` every row of `input` is
+    /// looked up, with it the unselected rows look up the first table row instead.
+    pub fn configure<F: FieldExt>(
+        &self,
+        meta: &mut ConstraintSystem<F>,
+        input: [Column<Advice>; 3],
+        q_lookup: Option<Selector>,
+    ) -> [TableColumn; 3] {
+        // we must enable equality so that chunks can be copied into lookup rows
+        for column in input.iter() {
+            meta.enable_equality((*column).into());
+        }
+
+        let table = [
+            meta.lookup_table_column(),
+            meta.lookup_table_column(),
+            meta.lookup_table_column(),
+        ];
+        let default = (self.row)(0);
+
+        meta.lookup(|meta| {
+            input
+                .iter()
+                .zip(default)
+                .zip(table)
+                .map(|((column, default), table)| {
+                    let value = meta.query_advice(*column, Rotation::cur());
+
+                    let value = match q_lookup {
+                        Some(q_lookup) => {
+                            let q_lookup = meta.query_selector(q_lookup);
+                            let not_q_lookup = Expression::Constant(F::one()) - q_lookup.clone();
+                            q_lookup * value + not_q_lookup * F::from(default)
+                        }
+                        None => value,
+                    };
+                    (value, table)
+                })
+                .collect()
+        });
+
+        table
+    }
+
+    pub fn load<F: FieldExt>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        table: [TableColumn; 3],
+    ) -> Result<(), Error> {
+        layouter.assign_table(
+            || self.name,
+            |mut assignment| {
+                for row_offset in 0..self.len {
+                    for (column, value) in table.iter().zip((self.row)(row_offset)) {
+                        assignment.assign_cell(
+                            || format!("{} row {}", self.name, row_offset),
+                            *column,
+                            row_offset,
+                            || Ok(F::from(value)),
+                        )?;
+                    }
+                }
+                Ok(())
+            },
+        )
+    }
+}
@@ -1,2 +1,20 @@
+pub mod aes;
+pub mod bitwise;
+pub mod bytes;
+pub mod chi;
+pub mod compare;
+pub mod crc32;
 pub mod decompose;
-pub mod xor;
\ No newline at end of file
+pub mod fused_xor;
+pub mod hmac;
+pub mod keccak;
+pub mod lookup;
+pub mod murmur3;
+pub mod popcount;
+pub mod ripemd160;
+pub mod select;
+pub mod sha2;
+pub mod sha256;
+pub mod sha512;
+pub mod xor;
+pub mod xxhash;
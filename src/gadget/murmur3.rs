@@ -0,0 +1,295 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{ConstraintSystem, Error},
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::{
+    bitwise::{Bitwise32Chip, Bitwise32Config, BitwiseInstructions},
+    xor::TableChip,
+    xxhash::avalanche,
+};
+use crate::word::{AssignedChunk, AssignedWord32, Word32};
+
+const C1: u32 = 0xcc9e2d51;
+const C2: u32 = 0x1b873593;
+
+/// Native MurmurHash3_x86_32, the reference for the chip
+pub fn murmur3_32(bytes: &[u8], seed: u32) -> u32 {
+    let scramble = |k: u32| k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+
+    let blocks = bytes.chunks_exact(4);
+    let tail = blocks.remainder();
+
+    let mut h = seed;
+    for block in blocks {
+        h ^= scramble(u32::from_le_bytes(block.try_into().unwrap()));
+        h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe6546b64);
+    }
+
+    if !tail.is_empty() {
+        let k = tail.iter().rev().fold(0, |k, byte| k << 8 | *byte as u32);
+        h ^= scramble(k);
+    }
+
+    h ^= bytes.len() as u32;
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85ebca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2ae35);
+    h ^ (h >> 16)
+}
+
+#[derive(Clone, Debug)]
+pub struct Murmur3Config {
+    pub bitwise_config: Bitwise32Config,
+}
+
+#[derive(Clone, Debug)]
+pub struct Murmur3Chip<F> {
+    config: Murmur3Config,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for Murmur3Chip<F> {
+    type Config = Murmur3Config;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> Murmur3Chip<F> {
+    pub fn configure(
+        _meta: &mut ConstraintSystem<F>,
+        bitwise_config: Bitwise32Config,
+    ) -> Murmur3Config {
+        Murmur3Config {
+            bitwise_config
+        }
+    }
+
+    pub fn construct(config: Murmur3Config) -> Self {
+        Murmur3Chip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: Murmur3Config,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.bitwise_config.table_config, layouter)
+    }
+
+    fn bitwise_chip(&self) -> Bitwise32Chip<F> {
+        Bitwise32Chip::construct(self.config.bitwise_config.clone())
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.bitwise_config.table_config.clone())
+    }
+
+    /// Witnesses the input bytes, range checked through the xor table
+    pub fn load_bytes(
+        &self,
+        layouter: impl Layouter<F>,
+        bytes: &[Option<u8>],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        self.table_chip().load_chunks(layouter, bytes)
+    }
+
+    /// MurmurHash3_x86_32 of range checked `bytes` with a fixed `seed`
+    pub fn hash(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[AssignedChunk<F>],
+        seed: u32,
+    ) -> Result<AssignedWord32<F>, Error> {
+        let chip = self.bitwise_chip();
+
+        let blocks = bytes.chunks_exact(4);
+        let tail = blocks.remainder();
+
+        let mut h = chip.constant(layouter.namespace(|| "seed"), Word32::new(seed as u64))?;
+
+        for (i, block) in blocks.enumerate() {
+            let mut layouter = layouter.namespace(|| format!("block {}", i));
+
+            let k = chip.compose_le_padded(layouter.namespace(|| "lane"), block)?;
+            let k = self.scramble(layouter.namespace(|| "scramble"), &k)?;
+            h = chip.xor(layouter.namespace(|| "xor"), &h, &k)?;
+            h = chip.rotl(layouter.namespace(|| "rotl"), &h, 13)?;
+            h = chip.mul_constant(layouter.namespace(|| "mul"), &h, Word32::new(5))?;
+            let c = chip.constant(layouter.namespace(|| "constant"), Word32::new(0xe6546b64))?;
+            h = chip.add(layouter.namespace(|| "add"), &h, &c)?;
+        }
+
+        if !tail.is_empty() {
+            let k = chip.compose_le_padded(layouter.namespace(|| "tail lane"), tail)?;
+            let k = self.scramble(layouter.namespace(|| "tail scramble"), &k)?;
+            h = chip.xor(layouter.namespace(|| "tail xor"), &h, &k)?;
+        }
+
+        let len = chip.constant(layouter.namespace(|| "len"), Word32::new(bytes.len() as u64))?;
+        h = chip.xor(layouter.namespace(|| "xor len"), &h, &len)?;
+
+        let steps = [
+            (16, Some(Word32::new(0x85ebca6b))),
+            (13, Some(Word32::new(0xc2b2ae35))),
+            (16, None),
+        ];
+        avalanche(&chip, layouter.namespace(|| "fmix"), h, &steps)
+    }
+
+    /// rotl(k * c1, 15) * c2
+    fn scramble(
+        &self,
+        mut layouter: impl Layouter<F>,
+        k: &AssignedWord32<F>,
+    ) -> Result<AssignedWord32<F>, Error> {
+        let chip = self.bitwise_chip();
+
+        let k = chip.mul_constant(layouter.namespace(|| "mul c1"), k, Word32::new(C1 as u64))?;
+        let k = chip.rotl(layouter.namespace(|| "rotl"), &k, 15)?;
+        chip.mul_constant(layouter.namespace(|| "mul c2"), &k, Word32::new(C2 as u64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use std::convert::TryInto;
+
+    use super::{murmur3_32, Murmur3Chip, Murmur3Config};
+
+    use crate::gadget::{
+        bitwise::Bitwise32Chip,
+        decompose::Decompose32Chip,
+        xor::TableChip,
+    };
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        murmur3_config: Murmur3Config,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        bytes: Vec<Option<u8>>,
+        seed: u32,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                bytes: vec![None; self.bytes.len()],
+                seed: self.seed,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_config = Bitwise32Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_config,
+                table_config
+            );
+            let murmur3_config = Murmur3Chip::configure(meta, bitwise_config);
+
+            Config {
+                instance,
+                murmur3_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            Murmur3Chip::<F>::load(config.murmur3_config.clone(), &mut layouter)?;
+            let chip = Murmur3Chip::<F>::construct(config.murmur3_config.clone());
+
+            let bytes = chip.load_bytes(layouter.namespace(|| "bytes"), &self.bytes)?;
+            let hash = chip.hash(layouter.namespace(|| "murmur3_32"), &bytes, self.seed)?;
+
+            layouter.constrain_instance(hash.cell(), config.instance, 0)
+        }
+    }
+
+    fn circuit(bytes: &[u8], seed: u32) -> Circuit {
+        Circuit {
+            bytes: bytes.iter().copied().map(Some).collect(),
+            seed,
+        }
+    }
+
+    #[test]
+    fn native_murmur3_32() {
+        assert_eq!(murmur3_32(b"", 0), 0);
+        assert_eq!(murmur3_32(b"", 1), 0x514e28b7);
+        assert_eq!(murmur3_32(b"hello", 0), 0x248bfa47);
+        assert_eq!(murmur3_32(b"The quick brown fox jumps over the lazy dog", 0), 0x2e4ff723);
+    }
+
+    fn murmur3_32_test<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        // tail only, and blocks followed by a tail
+        let long: Vec<u8> = (0..11).map(|_| rng.gen()).collect();
+        let inputs: [(&[u8], u32); 3] = [(b"", 0), (b"abc", 1), (&long, rng.gen())];
+
+        for (bytes, seed) in inputs {
+            let expected = F::from(murmur3_32(bytes, seed) as u64);
+            let prover = MockProver::<F>::run(k, &circuit(bytes, seed), vec![vec![expected]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn murmur3_32_wrong_hash<F: FieldExt>() {
+        let k = 17;
+
+        let expected = F::from(murmur3_32(b"abc", 0) as u64);
+        let prover = MockProver::<F>::run(k, &circuit(b"abd", 0), vec![vec![expected]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(murmur3_32_test, murmur3_32_wrong_hash);
+}
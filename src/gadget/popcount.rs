@@ -0,0 +1,380 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector, TableColumn},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+use crate::gadget::decompose::{DecomposeChip, DecomposeConfig, DecomposeInstruction};
+use crate::gadget::lookup::LookupTable;
+use crate::gates::Gate;
+use crate::word::{AssignedBit, AssignedWord, Bit, Chunk, Word};
+
+fn popcount_entry(row: usize) -> [u64; 3] {
+    let byte = row as u8;
+    [byte as u64, byte.count_ones() as u64, (byte.count_ones() & 1) as u64]
+}
+
+const POPCOUNT_TABLE: LookupTable = LookupTable {
+    name: "popcount table",
+    len: 1 << 8,
+    row: popcount_entry,
+};
+
+/// Lookup rows of (byte, popcount, parity)
+#[derive(Clone, Debug)]
+pub struct PopcountTableConfig {
+    pub q_lookup: Selector,
+    pub input: [Column<Advice>; 3],
+    pub table: [TableColumn; 3],
+}
+
+#[derive(Clone, Debug)]
+pub struct PopcountTableChip<F: FieldExt> {
+    config: PopcountTableConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for PopcountTableChip<F> {
+    type Config = PopcountTableConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> PopcountTableChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        input: [Column<Advice>; 3],
+    ) -> <Self as Chip<F>>::Config {
+        // (0, 0, 0) is the first table row, so unselected rows look it up
+        let q_lookup = meta.complex_selector();
+        let table = POPCOUNT_TABLE.configure(meta, input, Some(q_lookup));
+
+        PopcountTableConfig {
+            q_lookup,
+            input,
+            table,
+        }
+    }
+
+    pub fn construct(config: PopcountTableConfig) -> Self {
+        PopcountTableChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: PopcountTableConfig,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<<Self as Chip<F>>::Loaded, Error> {
+        POPCOUNT_TABLE.load(layouter, config.table)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PopcountConfig<const BITS: usize, const LIMBS: usize> {
+    pub q_popcount: Selector,
+    pub advice: [Column<Advice>; 2],
+    pub popcount_table_config: PopcountTableConfig,
+    pub decompose_config: DecomposeConfig<BITS, LIMBS>,
+}
+
+pub type Popcount32Config = PopcountConfig<32, 4>;
+
+/// Hamming weight and parity of a word, summed over the byte lookups of its decomposition
+#[derive(Clone, Debug)]
+pub struct PopcountChip<F, const BITS: usize, const LIMBS: usize> {
+    config: PopcountConfig<BITS, LIMBS>,
+    _marker: PhantomData<F>,
+}
+
+pub type Popcount32Chip<F> = PopcountChip<F, 32, 4>;
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> Chip<F> for PopcountChip<F, BITS, LIMBS> {
+    type Config = PopcountConfig<BITS, LIMBS>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> PopcountChip<F, BITS, LIMBS> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 2],
+        constants: Column<Fixed>,
+        popcount_table_config: PopcountTableConfig,
+        decompose_config: DecomposeConfig<BITS, LIMBS>,
+    ) -> PopcountConfig<BITS, LIMBS> {
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+        meta.enable_constant(constants);
+
+        let config = PopcountConfig {
+            q_popcount: meta.selector(),
+            advice,
+            popcount_table_config,
+            decompose_config,
+        };
+
+        // byte, count, parity | acc, parity acc
+        meta.create_gate("popcount", |meta| {
+            let q_popcount = meta.query_selector(config.q_popcount);
+            let input = config.popcount_table_config.input;
+
+            let count = meta.query_advice(input[1], Rotation::cur());
+            let parity = meta.query_advice(input[2], Rotation::cur());
+            let acc_prev = meta.query_advice(config.advice[0], Rotation::prev());
+            let acc = meta.query_advice(config.advice[0], Rotation::cur());
+            let parity_acc_prev = meta.query_advice(config.advice[1], Rotation::prev());
+            let parity_acc = meta.query_advice(config.advice[1], Rotation::cur());
+
+            Gate::popcount(q_popcount, count, parity, acc_prev, acc, parity_acc_prev, parity_acc)
+        });
+
+        config
+    }
+
+    pub fn construct(config: PopcountConfig<BITS, LIMBS>) -> Self {
+        PopcountChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: PopcountConfig<BITS, LIMBS>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        PopcountTableChip::load(config.popcount_table_config, layouter)
+    }
+
+    /// Number of set bits of a range checked word
+    pub fn popcount(
+        &self,
+        layouter: impl Layouter<F>,
+        word: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let (count, _) = self.count(layouter, word)?;
+        Ok(count)
+    }
+
+    /// Xor of all bits of a range checked word
+    pub fn parity(
+        &self,
+        layouter: impl Layouter<F>,
+        word: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedBit<F>, Error> {
+        let (_, parity) = self.count(layouter, word)?;
+        Ok(parity)
+    }
+
+    fn count(
+        &self,
+        mut layouter: impl Layouter<F>,
+        word: &AssignedWord<F, BITS>,
+    ) -> Result<(AssignedWord<F, BITS>, AssignedBit<F>), Error> {
+        let config = self.config();
+        let input = config.popcount_table_config.input;
+
+        let decompose_chip = DecomposeChip::<F, BITS, LIMBS>::construct(config.decompose_config.clone());
+        let chunks = decompose_chip.decompose(layouter.namespace(|| "decompose"), word.clone())?;
+
+        layouter.assign_region(
+            || "popcount",
+            |mut region| {
+                // both accumulators start from zero
+                let mut acc = AssignedWord::<F, BITS>::new(region.assign_advice_from_constant(
+                    || "acc 0",
+                    config.advice[0],
+                    0,
+                    Word::<BITS>::new(0),
+                )?);
+                let mut parity_acc = AssignedBit::new(region.assign_advice_from_constant(
+                    || "parity acc 0",
+                    config.advice[1],
+                    0,
+                    Bit::new(0),
+                )?);
+
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let row = i + 1;
+                    config.q_popcount.enable(&mut region, row)?;
+                    config.popcount_table_config.q_lookup.enable(&mut region, row)?;
+
+                    chunk.copy(|| format!("byte {}", i), &mut region, input[0], row)?;
+
+                    let count = chunk.value_word().map(|byte| Chunk::new(byte.count_ones() as u64));
+                    let parity = count.map(|count| Chunk::new(*count & 1));
+
+                    region.assign_advice(|| format!("count {}", i), input[1], row, || count.ok_or(Error::Synthesis))?;
+                    region.assign_advice(|| format!("parity {}", i), input[2], row, || parity.ok_or(Error::Synthesis))?;
+
+                    let acc_value = acc.value_word().zip(count)
+                        .map(|(acc, count)| Word::<BITS>::new(*acc + *count));
+                    let parity_acc_value = parity_acc.value_word().zip(parity)
+                        .map(|(parity_acc, parity)| Bit::new(*parity_acc ^ *parity));
+
+                    acc = AssignedWord::new(region.assign_advice(
+                        || format!("acc {}", row),
+                        config.advice[0],
+                        row,
+                        || acc_value.ok_or(Error::Synthesis),
+                    )?);
+                    parity_acc = AssignedBit::new(region.assign_advice(
+                        || format!("parity acc {}", row),
+                        config.advice[1],
+                        row,
+                        || parity_acc_value.ok_or(Error::Synthesis),
+                    )?);
+                }
+
+                Ok((acc, parity_acc))
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use std::convert::TryInto;
+
+    use super::{Popcount32Chip, Popcount32Config, PopcountTableChip};
+
+    use crate::gadget::decompose::{Decompose32Chip, DecomposeInstruction};
+    use crate::tamper::Tampering;
+    use crate::word::{AssignedWord32, Word32};
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        popcount_config: Popcount32Config,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        a: Option<Word32>,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 5] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let popcount_table_config = PopcountTableChip::configure(meta, advice[0..3].try_into().unwrap());
+            let popcount_config = Popcount32Chip::configure(
+                meta,
+                advice[3..5].try_into().unwrap(),
+                constants,
+                popcount_table_config,
+                decompose_config,
+            );
+
+            Config {
+                instance,
+                popcount_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            Popcount32Chip::<F>::load(config.popcount_config.clone(), &mut layouter)?;
+            let chip = Popcount32Chip::<F>::construct(config.popcount_config.clone());
+
+            // loaded into the accumulator column, which no lookup reads
+            let a = AssignedWord32::assign_word(layouter.namespace(|| "a"), config.popcount_config.advice[0], self.a)?;
+            Decompose32Chip::<F>::construct(config.popcount_config.decompose_config.clone())
+                .decompose(layouter.namespace(|| "range check a"), a.clone())?;
+
+            let count = chip.popcount(layouter.namespace(|| "popcount"), &a)?;
+            let parity = chip.parity(layouter.namespace(|| "parity"), &a)?;
+
+            layouter.constrain_instance(count.cell(), config.instance, 0)?;
+            layouter.constrain_instance(parity.cell(), config.instance, 1)
+        }
+    }
+
+    fn expected<F: FieldExt>(a: u32) -> Vec<F> {
+        vec![
+            F::from(a.count_ones() as u64),
+            F::from((a.count_ones() & 1) as u64),
+        ]
+    }
+
+    fn circuit(a: u32) -> Circuit {
+        Circuit {
+            a: Some(Word32::new(a as u64)),
+        }
+    }
+
+    fn popcount_test<F: FieldExt>() {
+        let k = 9;
+        let mut rng = rand::thread_rng();
+
+        for a in [0, 1, u32::MAX, 0x80000001, 0x0f0f0f0f, rng.gen()] {
+            let prover = MockProver::<F>::run(k, &circuit(a), vec![expected(a)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn popcount_wrong_output<F: FieldExt>() {
+        let k = 9;
+
+        let mut public_inputs = expected(0x0f0f0f0f);
+        public_inputs[0] = F::from(15);
+
+        let prover = MockProver::<F>::run(k, &circuit(0x0f0f0f0f), vec![public_inputs]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn popcount_tampering<F: FieldExt>() {
+        let k = 9;
+
+        Tampering::new(k, &circuit(0x0f0f0f0f), vec![expected::<F>(0x0f0f0f0f)]).assert_sound();
+    }
+
+    pasta_tests!(popcount_test, popcount_wrong_output, popcount_tampering);
+}
@@ -0,0 +1,439 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{ConstraintSystem, Error},
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::bitwise::{Bitwise32Chip, Bitwise32Config, BitwiseInstructions};
+use crate::word::{AssignedWord32, Word32};
+
+pub const BLOCK_SIZE: usize = 16;
+pub const DIGEST_SIZE: usize = 5;
+
+pub const IV: [u32; DIGEST_SIZE] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+const K_LEFT: [u32; 5] = [0x00000000, 0x5A827999, 0x6ED9EBA1, 0x8F1BBCDC, 0xA953FD4E];
+const K_RIGHT: [u32; 5] = [0x50A28BE6, 0x5C4DD124, 0x6D703EF3, 0x7A6D76E9, 0x00000000];
+
+const R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+const R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+const S_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+const S_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// Pads `message` and splits it into blocks of little-endian words
+pub fn pad(message: &[u8]) -> Vec<[Word32; BLOCK_SIZE]> {
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((message.len() as u64) * 8).to_le_bytes());
+
+    padded
+        .chunks(64)
+        .map(|block| {
+            let words: Vec<Word32> = block
+                .chunks(4)
+                .map(|bytes| Word32::new(u32::from_le_bytes(bytes.try_into().unwrap()) as u64))
+                .collect();
+            words.try_into().unwrap()
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug)]
+pub struct Ripemd160Config {
+    pub bitwise_config: Bitwise32Config,
+}
+
+#[derive(Clone, Debug)]
+pub struct Ripemd160Chip<F> {
+    config: Ripemd160Config,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for Ripemd160Chip<F> {
+    type Config = Ripemd160Config;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> Ripemd160Chip<F> {
+    pub fn configure(
+        _meta: &mut ConstraintSystem<F>,
+        bitwise_config: Bitwise32Config,
+    ) -> Ripemd160Config {
+        Ripemd160Config {
+            bitwise_config
+        }
+    }
+
+    pub fn construct(config: Ripemd160Config) -> Self {
+        Ripemd160Chip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    fn bitwise_chip(&self) -> Bitwise32Chip<F> {
+        Bitwise32Chip::construct(self.config.bitwise_config.clone())
+    }
+
+    /// Witnesses and range checks the words of a message block
+    pub fn load_block(
+        &self,
+        mut layouter: impl Layouter<F>,
+        block: [Option<Word32>; BLOCK_SIZE],
+    ) -> Result<[AssignedWord32<F>; BLOCK_SIZE], Error> {
+        let bitwise_chip = self.bitwise_chip();
+
+        let words = block
+            .iter()
+            .enumerate()
+            .map(|(i, word)| bitwise_chip.load_word(layouter.namespace(|| format!("word {}", i)), *word))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(words.try_into().unwrap())
+    }
+
+    pub fn initial_state(
+        &self,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<[AssignedWord32<F>; DIGEST_SIZE], Error> {
+        let bitwise_chip = self.bitwise_chip();
+
+        let state = IV
+            .iter()
+            .enumerate()
+            .map(|(i, h)| bitwise_chip.constant(layouter.namespace(|| format!("iv {}", i)), Word32::new(*h as u64)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(state.try_into().unwrap())
+    }
+
+    /// Hashes already padded message blocks, see [`pad`]
+    pub fn digest(
+        &self,
+        mut layouter: impl Layouter<F>,
+        blocks: &[[AssignedWord32<F>; BLOCK_SIZE]],
+    ) -> Result<[AssignedWord32<F>; DIGEST_SIZE], Error> {
+        let mut state = self.initial_state(layouter.namespace(|| "initial state"))?;
+
+        for (i, block) in blocks.iter().enumerate() {
+            state = self.compress(layouter.namespace(|| format!("compress block {}", i)), &state, block)?;
+        }
+
+        Ok(state)
+    }
+
+    pub fn compress(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &[AssignedWord32<F>; DIGEST_SIZE],
+        block: &[AssignedWord32<F>; BLOCK_SIZE],
+    ) -> Result<[AssignedWord32<F>; DIGEST_SIZE], Error> {
+        let bitwise_chip = self.bitwise_chip();
+
+        let mut left = state.clone();
+        let mut right = state.clone();
+
+        for j in 0..80 {
+            let round = j / 16;
+
+            left = self.step(
+                layouter.namespace(|| format!("left step {}", j)),
+                &left,
+                round,
+                &block[R_LEFT[j]],
+                K_LEFT[round],
+                S_LEFT[j],
+            )?;
+
+            right = self.step(
+                layouter.namespace(|| format!("right step {}", j)),
+                &right,
+                4 - round,
+                &block[R_RIGHT[j]],
+                K_RIGHT[round],
+                S_RIGHT[j],
+            )?;
+        }
+
+        let mut add3 = |name: &str, a: &AssignedWord32<F>, b: &AssignedWord32<F>, c: &AssignedWord32<F>| -> Result<AssignedWord32<F>, Error> {
+            let sum = bitwise_chip.add(layouter.namespace(|| format!("{} first", name)), a, b)?;
+            bitwise_chip.add(layouter.namespace(|| format!("{} second", name)), &sum, c)
+        };
+
+        Ok([
+            add3("h0", &state[1], &left[2], &right[3])?,
+            add3("h1", &state[2], &left[3], &right[4])?,
+            add3("h2", &state[3], &left[4], &right[0])?,
+            add3("h3", &state[4], &left[0], &right[1])?,
+            add3("h4", &state[0], &left[1], &right[2])?,
+        ])
+    }
+
+    /// One step of either line, `state` is (A, B, C, D, E)
+    fn step(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &[AssignedWord32<F>; DIGEST_SIZE],
+        function: usize,
+        x: &AssignedWord32<F>,
+        k: u32,
+        s: u32,
+    ) -> Result<[AssignedWord32<F>; DIGEST_SIZE], Error> {
+        let bitwise_chip = self.bitwise_chip();
+        let [a, b, c, d, e] = state;
+
+        let f = self.f(layouter.namespace(|| "f"), function, b, c, d)?;
+
+        let t = bitwise_chip.add(layouter.namespace(|| "a + f"), a, &f)?;
+        let t = bitwise_chip.add(layouter.namespace(|| "+ x"), &t, x)?;
+        let t = if k != 0 {
+            let k = bitwise_chip.constant(layouter.namespace(|| "k"), Word32::new(k as u64))?;
+            bitwise_chip.add(layouter.namespace(|| "+ k"), &t, &k)?
+        } else {
+            t
+        };
+        let t = bitwise_chip.rotl(layouter.namespace(|| "rotl s"), &t, s)?;
+        let t = bitwise_chip.add(layouter.namespace(|| "+ e"), &t, e)?;
+
+        let c = bitwise_chip.rotl(layouter.namespace(|| "rotl 10"), c, 10)?;
+
+        Ok([e.clone(), t, b.clone(), c, d.clone()])
+    }
+
+    /// The five nonlinear functions f1..f5, indexed from 0
+    fn f(
+        &self,
+        mut layouter: impl Layouter<F>,
+        function: usize,
+        x: &AssignedWord32<F>,
+        y: &AssignedWord32<F>,
+        z: &AssignedWord32<F>,
+    ) -> Result<AssignedWord32<F>, Error> {
+        let chip = self.bitwise_chip();
+
+        match function {
+            // x ^ y ^ z
+            0 => {
+                let t = chip.xor(layouter.namespace(|| "x ^ y"), x, y)?;
+                chip.xor(layouter.namespace(|| "^ z"), &t, z)
+            }
+            // (x & y) | (!x & z)
+            1 => {
+                let xy = chip.and(layouter.namespace(|| "x & y"), x, y)?;
+                let not_x = chip.not(layouter.namespace(|| "!x"), x)?;
+                let xz = chip.and(layouter.namespace(|| "!x & z"), &not_x, z)?;
+                chip.or(layouter.namespace(|| "|"), &xy, &xz)
+            }
+            // (x | !y) ^ z
+            2 => {
+                let not_y = chip.not(layouter.namespace(|| "!y"), y)?;
+                let t = chip.or(layouter.namespace(|| "x | !y"), x, &not_y)?;
+                chip.xor(layouter.namespace(|| "^ z"), &t, z)
+            }
+            // (x & z) | (y & !z)
+            3 => {
+                let xz = chip.and(layouter.namespace(|| "x & z"), x, z)?;
+                let not_z = chip.not(layouter.namespace(|| "!z"), z)?;
+                let yz = chip.and(layouter.namespace(|| "y & !z"), y, &not_z)?;
+                chip.or(layouter.namespace(|| "|"), &xz, &yz)
+            }
+            // x ^ (y | !z)
+            4 => {
+                let not_z = chip.not(layouter.namespace(|| "!z"), z)?;
+                let t = chip.or(layouter.namespace(|| "y | !z"), y, &not_z)?;
+                chip.xor(layouter.namespace(|| "x ^"), x, &t)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use std::convert::TryInto;
+
+    use super::{pad, Ripemd160Chip, Ripemd160Config, BLOCK_SIZE};
+
+    use crate::gadget::{
+        bitwise::Bitwise32Chip,
+        decompose::Decompose32Chip,
+        xor::TableChip,
+    };
+    use crate::word::Word32;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        ripemd160_config: Ripemd160Config
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        blocks: Vec<[Option<Word32>; BLOCK_SIZE]>
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                blocks: vec![[None; BLOCK_SIZE]; self.blocks.len()]
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_config = Bitwise32Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_config,
+                table_config
+            );
+            let ripemd160_config = Ripemd160Chip::configure(meta, bitwise_config);
+
+            Config {
+                instance,
+                ripemd160_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.ripemd160_config.bitwise_config.table_config.clone(), &mut layouter)?;
+            let chip = Ripemd160Chip::<F>::construct(config.ripemd160_config.clone());
+
+            let blocks = self.blocks
+                .iter()
+                .enumerate()
+                .map(|(i, block)| chip.load_block(layouter.namespace(|| format!("load block {}", i)), *block))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let digest = chip.digest(layouter.namespace(|| "ripemd160"), &blocks)?;
+
+            for (row, word) in digest.iter().enumerate() {
+                layouter.constrain_instance(word.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn circuit(message: &[u8]) -> Circuit {
+        Circuit {
+            blocks: pad(message)
+                .into_iter()
+                .map(|block| block.map(Some))
+                .collect()
+        }
+    }
+
+    fn digest_words<F: FieldExt>(hex: &str) -> Vec<F> {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+
+        bytes
+            .chunks(4)
+            .map(|word| F::from(u32::from_le_bytes(word.try_into().unwrap()) as u64))
+            .collect()
+    }
+
+    fn ripemd160_test_vectors<F: FieldExt>() {
+        let k = 17;
+
+        let vectors: [(&[u8], &str); 4] = [
+            (b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+            (b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            (b"message digest", "5d0689ef49d2fae572b881b123a85ffa21595f36"),
+            (
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "12a053384a9c0c88e405a06c27dcf49ada62eb2b"
+            ),
+        ];
+
+        for (message, expected) in vectors {
+            let prover = MockProver::<F>::run(k, &circuit(message), vec![digest_words(expected)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn ripemd160_wrong_digest<F: FieldExt>() {
+        let k = 17;
+
+        let prover = MockProver::<F>::run(
+            k,
+            &circuit(b"abd"),
+            vec![digest_words("8eb208f7e05d987a9b044a8e98c6b087f15a0bfc")]
+        ).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(ripemd160_test_vectors, ripemd160_wrong_digest);
+}
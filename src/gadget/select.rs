@@ -0,0 +1,237 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{Advice, Column, ConstraintSystem, Error, Selector},
+    poly::Rotation,
+};
+use std::marker::PhantomData;
+
+use crate::gates::Gate;
+use crate::word::{AssignedBit, AssignedChunk, AssignedWord};
+
+#[derive(Clone, Debug)]
+pub struct SelectConfig {
+    pub q_select: Selector,
+    pub advice: [Column<Advice>; 3],
+}
+
+/// `cond ? a : b` on words, with `cond` constrained to be boolean
+#[derive(Clone, Debug)]
+pub struct SelectChip<F> {
+    config: SelectConfig,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for SelectChip<F> {
+    type Config = SelectConfig;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> SelectChip<F> {
+    pub fn configure(
+        meta: &mut ConstraintSystem<F>,
+        advice: [Column<Advice>; 3],
+    ) -> SelectConfig {
+        for advice in advice.iter() {
+            meta.enable_equality((*advice).into());
+        }
+
+        let config = SelectConfig {
+            q_select: meta.selector(),
+            advice,
+        };
+
+        // cond, a, b
+        // out
+        meta.create_gate("select", |meta| {
+            let q_select = meta.query_selector(config.q_select);
+
+            let cond = meta.query_advice(config.advice[0], Rotation::cur());
+            let a = meta.query_advice(config.advice[1], Rotation::cur());
+            let b = meta.query_advice(config.advice[2], Rotation::cur());
+            let out = meta.query_advice(config.advice[0], Rotation::next());
+
+            Gate::select(q_select, cond, a, b, out)
+        });
+
+        config
+    }
+
+    pub fn construct(config: SelectConfig) -> Self {
+        SelectChip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    /// `cond ? a : b`, the result is range checked whenever `a` and `b` are
+    pub fn select<const BITS: usize>(
+        &self,
+        mut layouter: impl Layouter<F>,
+        cond: &AssignedBit<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let config = self.config();
+
+        let out = cond.value_word().zip(a.value_word()).zip(b.value_word())
+            .map(|((cond, a), b)| if *cond == 1 { a } else { b });
+
+        layouter.assign_region(
+            || "select",
+            |mut region| {
+                config.q_select.enable(&mut region, 0)?;
+
+                cond.copy(|| "copy cond", &mut region, config.advice[0], 0)?;
+                a.copy(|| "copy a", &mut region, config.advice[1], 0)?;
+                b.copy(|| "copy b", &mut region, config.advice[2], 0)?;
+
+                let assigned = region.assign_advice(
+                    || "out",
+                    config.advice[0],
+                    1,
+                    || out.ok_or(Error::Synthesis),
+                )?;
+
+                Ok(AssignedWord::<F, BITS>::new(assigned))
+            },
+        )
+    }
+
+    pub fn select_chunk(
+        &self,
+        layouter: impl Layouter<F>,
+        cond: &AssignedBit<F>,
+        a: &AssignedChunk<F>,
+        b: &AssignedChunk<F>,
+    ) -> Result<AssignedChunk<F>, Error> {
+        self.select(layouter, cond, a, b)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+
+    use super::{SelectChip, SelectConfig};
+
+    use crate::tamper::Tampering;
+    use crate::word::{AssignedBit, AssignedChunk, AssignedWord32, Bit, Chunk, Word32};
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        select_config: SelectConfig,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        cond: Option<Bit>,
+        a: Option<Word32>,
+        b: Option<Word32>,
+        x: Option<Chunk>,
+        y: Option<Chunk>,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let select_config = SelectChip::configure(meta, advice);
+
+            Config {
+                instance,
+                select_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let chip = SelectChip::<F>::construct(config.select_config.clone());
+            let advice = config.select_config.advice;
+
+            let cond = AssignedBit::assign_word(layouter.namespace(|| "cond"), advice[0], self.cond)?;
+            let a = AssignedWord32::assign_word(layouter.namespace(|| "a"), advice[1], self.a)?;
+            let b = AssignedWord32::assign_word(layouter.namespace(|| "b"), advice[2], self.b)?;
+            let x = AssignedChunk::assign_word(layouter.namespace(|| "x"), advice[1], self.x)?;
+            let y = AssignedChunk::assign_word(layouter.namespace(|| "y"), advice[2], self.y)?;
+
+            let word = chip.select(layouter.namespace(|| "select word"), &cond, &a, &b)?;
+            let chunk = chip.select_chunk(layouter.namespace(|| "select chunk"), &cond, &x, &y)?;
+
+            layouter.constrain_instance(word.cell(), config.instance, 0)?;
+            layouter.constrain_instance(chunk.cell(), config.instance, 1)
+        }
+    }
+
+    fn circuit(cond: bool, a: u32, b: u32, x: u8, y: u8) -> Circuit {
+        Circuit {
+            cond: Some(Bit::new(cond as u64)),
+            a: Some(Word32::new(a as u64)),
+            b: Some(Word32::new(b as u64)),
+            x: Some(Chunk::new(x as u64)),
+            y: Some(Chunk::new(y as u64)),
+        }
+    }
+
+    fn select_test<F: FieldExt>() {
+        let k = 4;
+
+        for cond in [false, true] {
+            let (a, b, x, y) = (0xdeadbeef, u32::MAX, 0x12, 0xff);
+            let expected = if cond { [a as u64, x] } else { [b as u64, y] };
+
+            let prover = MockProver::<F>::run(k, &circuit(cond, a, b, x as u8, y as u8), vec![expected.map(F::from).to_vec()]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn select_wrong_branch<F: FieldExt>() {
+        let k = 4;
+
+        let prover = MockProver::<F>::run(k, &circuit(true, 1, 2, 3, 4), vec![vec![F::from(2), F::from(4)]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    fn select_tampering<F: FieldExt>() {
+        let k = 4;
+
+        for cond in [false, true] {
+            let expected = if cond { [1, 3] } else { [2, 4] };
+            Tampering::new(k, &circuit(cond, 1, 2, 3, 4), vec![expected.map(F::from).to_vec()]).assert_sound();
+        }
+    }
+
+    pasta_tests!(select_test, select_wrong_branch, select_tampering);
+}
@@ -0,0 +1,272 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{ConstraintSystem, Error},
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::bitwise::{BitwiseChip, BitwiseConfig, BitwiseInstructions};
+use crate::word::{AssignedWord, Word};
+
+pub const BLOCK_SIZE: usize = 16;
+pub const DIGEST_SIZE: usize = 8;
+
+/// What tells the SHA-2 variants apart besides the word size
+#[derive(Debug)]
+pub struct Sha2Constants {
+    pub iv: [u64; DIGEST_SIZE],
+    /// One per round
+    pub round_constants: &'static [u64],
+    /// Rotations of Sigma0 and Sigma1
+    pub big_sigma: [(u32, u32, u32); 2],
+    /// Rotations and shift of sigma0 and sigma1
+    pub small_sigma: [(u32, u32, u32); 2],
+}
+
+#[derive(Clone, Debug)]
+pub struct Sha2Config<const BITS: usize, const LIMBS: usize> {
+    pub bitwise_config: BitwiseConfig<BITS, LIMBS>,
+    pub constants: &'static Sha2Constants,
+}
+
+/// SHA-2 compression on `BITS` wide words, SHA-256 and SHA-512 differ only in their constants
+#[derive(Clone, Debug)]
+pub struct Sha2Chip<F, const BITS: usize, const LIMBS: usize> {
+    config: Sha2Config<BITS, LIMBS>,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> Chip<F> for Sha2Chip<F, BITS, LIMBS> {
+    type Config = Sha2Config<BITS, LIMBS>;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt, const BITS: usize, const LIMBS: usize> Sha2Chip<F, BITS, LIMBS> {
+    pub fn configure(
+        _meta: &mut ConstraintSystem<F>,
+        bitwise_config: BitwiseConfig<BITS, LIMBS>,
+        constants: &'static Sha2Constants,
+    ) -> Sha2Config<BITS, LIMBS> {
+        Sha2Config {
+            bitwise_config,
+            constants,
+        }
+    }
+
+    pub fn construct(config: Sha2Config<BITS, LIMBS>) -> Self {
+        Sha2Chip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    fn bitwise_chip(&self) -> BitwiseChip<F, BITS, LIMBS> {
+        BitwiseChip::construct(self.config.bitwise_config.clone())
+    }
+
+    /// Witnesses and range checks the words of a message block
+    pub fn load_block(
+        &self,
+        mut layouter: impl Layouter<F>,
+        block: [Option<Word<BITS>>; BLOCK_SIZE],
+    ) -> Result<[AssignedWord<F, BITS>; BLOCK_SIZE], Error> {
+        let chip = self.bitwise_chip();
+
+        let words = block
+            .iter()
+            .enumerate()
+            .map(|(i, word)| chip.load_word(layouter.namespace(|| format!("word {}", i)), *word))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(words.try_into().unwrap())
+    }
+
+    pub fn initial_state(
+        &self,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<[AssignedWord<F, BITS>; DIGEST_SIZE], Error> {
+        let chip = self.bitwise_chip();
+
+        let state = self.config.constants.iv
+            .iter()
+            .enumerate()
+            .map(|(i, h)| chip.constant(layouter.namespace(|| format!("iv {}", i)), Word::new(*h)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(state.try_into().unwrap())
+    }
+
+    /// Hashes already padded message blocks
+    pub fn digest(
+        &self,
+        mut layouter: impl Layouter<F>,
+        blocks: &[[AssignedWord<F, BITS>; BLOCK_SIZE]],
+    ) -> Result<[AssignedWord<F, BITS>; DIGEST_SIZE], Error> {
+        let mut state = self.initial_state(layouter.namespace(|| "initial state"))?;
+
+        for (i, block) in blocks.iter().enumerate() {
+            state = self.compress(layouter.namespace(|| format!("compress block {}", i)), &state, block)?;
+        }
+
+        Ok(state)
+    }
+
+    pub fn compress(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &[AssignedWord<F, BITS>; DIGEST_SIZE],
+        block: &[AssignedWord<F, BITS>; BLOCK_SIZE],
+    ) -> Result<[AssignedWord<F, BITS>; DIGEST_SIZE], Error> {
+        let chip = self.bitwise_chip();
+
+        let schedule = self.message_schedule(layouter.namespace(|| "message schedule"), block)?;
+
+        let mut working = state.clone();
+        for (t, (w, k)) in schedule.iter().zip(self.config.constants.round_constants).enumerate() {
+            working = self.round(layouter.namespace(|| format!("round {}", t)), &working, w, *k)?;
+        }
+
+        let next = state
+            .iter()
+            .zip(working.iter())
+            .enumerate()
+            .map(|(i, (h, v))| chip.add(layouter.namespace(|| format!("h{}", i)), h, v))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(next.try_into().unwrap())
+    }
+
+    fn message_schedule(
+        &self,
+        mut layouter: impl Layouter<F>,
+        block: &[AssignedWord<F, BITS>; BLOCK_SIZE],
+    ) -> Result<Vec<AssignedWord<F, BITS>>, Error> {
+        let chip = self.bitwise_chip();
+        let [sigma0, sigma1] = self.config.constants.small_sigma;
+        let mut w = block.to_vec();
+
+        for t in BLOCK_SIZE..self.config.constants.round_constants.len() {
+            let mut layouter = layouter.namespace(|| format!("w{}", t));
+
+            let s0 = self.small_sigma(layouter.namespace(|| "sigma0"), &w[t - 15], sigma0)?;
+            let s1 = self.small_sigma(layouter.namespace(|| "sigma1"), &w[t - 2], sigma1)?;
+
+            let sum = chip.add(layouter.namespace(|| "s1 + w[t - 7]"), &s1, &w[t - 7])?;
+            let sum = chip.add(layouter.namespace(|| "+ s0"), &sum, &s0)?;
+            let sum = chip.add(layouter.namespace(|| "+ w[t - 16]"), &sum, &w[t - 16])?;
+
+            w.push(sum);
+        }
+
+        Ok(w)
+    }
+
+    /// One round, `state` is (a, b, c, d, e, f, g, h)
+    fn round(
+        &self,
+        mut layouter: impl Layouter<F>,
+        state: &[AssignedWord<F, BITS>; DIGEST_SIZE],
+        w: &AssignedWord<F, BITS>,
+        k: u64,
+    ) -> Result<[AssignedWord<F, BITS>; DIGEST_SIZE], Error> {
+        let chip = self.bitwise_chip();
+        let [sigma0, sigma1] = self.config.constants.big_sigma;
+        let [a, b, c, d, e, f, g, h] = state;
+
+        let s1 = self.big_sigma(layouter.namespace(|| "Sigma1"), e, sigma1)?;
+        let ch = self.ch(layouter.namespace(|| "ch"), e, f, g)?;
+        let k = chip.constant(layouter.namespace(|| "k"), Word::new(k))?;
+
+        let t1 = chip.add(layouter.namespace(|| "h + Sigma1"), h, &s1)?;
+        let t1 = chip.add(layouter.namespace(|| "+ ch"), &t1, &ch)?;
+        let t1 = chip.add(layouter.namespace(|| "+ k"), &t1, &k)?;
+        let t1 = chip.add(layouter.namespace(|| "+ w"), &t1, w)?;
+
+        let s0 = self.big_sigma(layouter.namespace(|| "Sigma0"), a, sigma0)?;
+        let maj = self.maj(layouter.namespace(|| "maj"), a, b, c)?;
+        let t2 = chip.add(layouter.namespace(|| "Sigma0 + maj"), &s0, &maj)?;
+
+        let new_e = chip.add(layouter.namespace(|| "d + t1"), d, &t1)?;
+        let new_a = chip.add(layouter.namespace(|| "t1 + t2"), &t1, &t2)?;
+
+        Ok([new_a, a.clone(), b.clone(), c.clone(), new_e, e.clone(), f.clone(), g.clone()])
+    }
+
+    /// rotr(x, r0) ^ rotr(x, r1) ^ rotr(x, r2)
+    fn big_sigma(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &AssignedWord<F, BITS>,
+        (r0, r1, r2): (u32, u32, u32),
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let chip = self.bitwise_chip();
+
+        let x0 = chip.rotr(layouter.namespace(|| "rotr r0"), x, r0)?;
+        let x1 = chip.rotr(layouter.namespace(|| "rotr r1"), x, r1)?;
+        let x2 = chip.rotr(layouter.namespace(|| "rotr r2"), x, r2)?;
+
+        let t = chip.xor(layouter.namespace(|| "x0 ^ x1"), &x0, &x1)?;
+        chip.xor(layouter.namespace(|| "^ x2"), &t, &x2)
+    }
+
+    /// rotr(x, r0) ^ rotr(x, r1) ^ (x >> s)
+    fn small_sigma(
+        &self,
+        mut layouter: impl Layouter<F>,
+        x: &AssignedWord<F, BITS>,
+        (r0, r1, s): (u32, u32, u32),
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let chip = self.bitwise_chip();
+
+        let x0 = chip.rotr(layouter.namespace(|| "rotr r0"), x, r0)?;
+        let x1 = chip.rotr(layouter.namespace(|| "rotr r1"), x, r1)?;
+        let x2 = chip.shr(layouter.namespace(|| "shr s"), x, s)?;
+
+        let t = chip.xor(layouter.namespace(|| "x0 ^ x1"), &x0, &x1)?;
+        chip.xor(layouter.namespace(|| "^ x2"), &t, &x2)
+    }
+
+    /// (e & f) ^ (!e & g)
+    fn ch(
+        &self,
+        mut layouter: impl Layouter<F>,
+        e: &AssignedWord<F, BITS>,
+        f: &AssignedWord<F, BITS>,
+        g: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let chip = self.bitwise_chip();
+
+        let ef = chip.and(layouter.namespace(|| "e & f"), e, f)?;
+        let not_e = chip.not(layouter.namespace(|| "!e"), e)?;
+        let not_e_g = chip.and(layouter.namespace(|| "!e & g"), &not_e, g)?;
+
+        chip.xor(layouter.namespace(|| "^"), &ef, &not_e_g)
+    }
+
+    /// (a & b) ^ (a & c) ^ (b & c)
+    fn maj(
+        &self,
+        mut layouter: impl Layouter<F>,
+        a: &AssignedWord<F, BITS>,
+        b: &AssignedWord<F, BITS>,
+        c: &AssignedWord<F, BITS>,
+    ) -> Result<AssignedWord<F, BITS>, Error> {
+        let chip = self.bitwise_chip();
+
+        let ab = chip.and(layouter.namespace(|| "a & b"), a, b)?;
+        let ac = chip.and(layouter.namespace(|| "a & c"), a, c)?;
+        let bc = chip.and(layouter.namespace(|| "b & c"), b, c)?;
+
+        let t = chip.xor(layouter.namespace(|| "ab ^ ac"), &ab, &ac)?;
+        chip.xor(layouter.namespace(|| "^ bc"), &t, &bc)
+    }
+}
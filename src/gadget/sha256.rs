@@ -0,0 +1,305 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::Error,
+};
+use std::convert::TryInto;
+
+use crate::gadget::{
+    decompose::{Decompose32Chip, DecomposeInstruction},
+    sha2::{Sha2Chip, Sha2Config, Sha2Constants},
+};
+use crate::word::{AssignedChunk, AssignedWord32, Chunk, Endianness, Word32};
+
+pub use crate::gadget::sha2::{BLOCK_SIZE, DIGEST_SIZE};
+
+const ROUNDS: usize = 64;
+
+const ROUND_CONSTANTS: [u64; ROUNDS] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// FIPS 180-4 SHA-256 constants
+pub const SHA256: Sha2Constants = Sha2Constants {
+    iv: [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ],
+    round_constants: &ROUND_CONSTANTS,
+    big_sigma: [(2, 13, 22), (6, 11, 25)],
+    small_sigma: [(7, 18, 3), (17, 19, 10)],
+};
+
+/// Bytes appended to a message of `len` bytes: 0x80, zeros and the big-endian bit length
+pub fn padding(len: usize) -> Vec<u8> {
+    let mut padding = vec![0x80];
+    while (len + padding.len()) % 64 != 56 {
+        padding.push(0);
+    }
+    padding.extend_from_slice(&((len as u64) * 8).to_be_bytes());
+    padding
+}
+
+/// Pads `message` and splits it into blocks of big-endian words
+pub fn pad(message: &[u8]) -> Vec<[Word32; BLOCK_SIZE]> {
+    let padded = [message, &padding(message.len())].concat();
+
+    padded
+        .chunks(64)
+        .map(|block| {
+            let words: Vec<Word32> = block
+                .chunks(4)
+                .map(|bytes| Word32::new(u32::from_be_bytes(bytes.try_into().unwrap()) as u64))
+                .collect();
+            words.try_into().unwrap()
+        })
+        .collect()
+}
+
+pub type Sha256Config = Sha2Config<32, 4>;
+pub type Sha256Chip<F> = Sha2Chip<F, 32, 4>;
+
+impl<F: FieldExt> Sha256Chip<F> {
+    fn decompose_chip(&self) -> Decompose32Chip<F> {
+        Decompose32Chip::construct(self.config().bitwise_config.decompose_config.clone())
+    }
+
+    /// Pads range checked `bytes` with constants and hashes them
+    pub fn hash_bytes(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[AssignedChunk<F>],
+    ) -> Result<[AssignedWord32<F>; DIGEST_SIZE], Error> {
+        let decompose_chip = self.decompose_chip();
+
+        let padding = self.constant_bytes(layouter.namespace(|| "padding"), &padding(bytes.len()))?;
+        let padded = [bytes, &padding].concat();
+
+        let words = padded
+            .chunks(4)
+            .enumerate()
+            .map(|(i, bytes)| {
+                let chunks: [AssignedChunk<F>; 4] = bytes.to_vec().try_into().unwrap();
+                decompose_chip.compose_with(layouter.namespace(|| format!("word {}", i)), chunks, Endianness::Big)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let blocks: Vec<[AssignedWord32<F>; BLOCK_SIZE]> = words
+            .chunks(BLOCK_SIZE)
+            .map(|block| block.to_vec().try_into().unwrap())
+            .collect();
+
+        self.digest(layouter.namespace(|| "digest"), &blocks)
+    }
+
+    /// Big-endian bytes of `digest`
+    pub fn digest_bytes(
+        &self,
+        mut layouter: impl Layouter<F>,
+        digest: &[AssignedWord32<F>; DIGEST_SIZE],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        let decompose_chip = self.decompose_chip();
+        let mut bytes = Vec::with_capacity(4 * DIGEST_SIZE);
+
+        for (i, word) in digest.iter().enumerate() {
+            let chunks = decompose_chip.decompose_with(layouter.namespace(|| format!("word {}", i)), word.clone(), Endianness::Big)?;
+            bytes.extend(chunks);
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn constant_bytes(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[u8],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        let config = &self.config().bitwise_config;
+
+        layouter.assign_region(
+            || "constant bytes",
+            |mut region| {
+                bytes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, byte)| {
+                        let assigned = region.assign_advice_from_constant(
+                            || format!("constant {}", i),
+                            config.advice[i % 3],
+                            i / 3,
+                            Chunk::new(*byte as u64),
+                        )?;
+
+                        Ok(AssignedChunk::new(assigned))
+                    })
+                    .collect()
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use sha2::{Digest, Sha256};
+    use std::convert::TryInto;
+
+    use super::{pad, Sha256Chip, Sha256Config, BLOCK_SIZE, SHA256};
+
+    use crate::gadget::{
+        bitwise::Bitwise32Chip,
+        decompose::Decompose32Chip,
+        xor::TableChip,
+    };
+    use crate::word::Word32;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        sha256_config: Sha256Config
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        blocks: Vec<[Option<Word32>; BLOCK_SIZE]>
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                blocks: vec![[None; BLOCK_SIZE]; self.blocks.len()]
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_config = Bitwise32Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_config,
+                table_config
+            );
+            let sha256_config = Sha256Chip::configure(meta, bitwise_config, &SHA256);
+
+            Config {
+                instance,
+                sha256_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.sha256_config.bitwise_config.table_config.clone(), &mut layouter)?;
+            let chip = Sha256Chip::<F>::construct(config.sha256_config.clone());
+
+            let blocks = self.blocks
+                .iter()
+                .enumerate()
+                .map(|(i, block)| chip.load_block(layouter.namespace(|| format!("load block {}", i)), *block))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let digest = chip.digest(layouter.namespace(|| "sha256"), &blocks)?;
+
+            for (row, word) in digest.iter().enumerate() {
+                layouter.constrain_instance(word.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn circuit(message: &[u8]) -> Circuit {
+        Circuit {
+            blocks: pad(message)
+                .into_iter()
+                .map(|block| block.map(Some))
+                .collect()
+        }
+    }
+
+    fn digest_words<F: FieldExt>(digest: &[u8]) -> Vec<F> {
+        digest
+            .chunks(4)
+            .map(|word| F::from(u32::from_be_bytes(word.try_into().unwrap()) as u64))
+            .collect()
+    }
+
+    fn hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn sha256_test_vectors<F: FieldExt>() {
+        let k = 17;
+
+        let vectors: [(&[u8], &str); 2] = [
+            (b"", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            (b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        ];
+
+        for (message, expected) in vectors {
+            let prover = MockProver::<F>::run(k, &circuit(message), vec![digest_words(&hex(expected))]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn sha256_random_messages<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        // one and two block messages
+        for len in [rng.gen_range(0..56), rng.gen_range(56..120)] {
+            let message: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let expected = Sha256::digest(&message);
+
+            let prover = MockProver::<F>::run(k, &circuit(&message), vec![digest_words(&expected)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn sha256_wrong_digest<F: FieldExt>() {
+        let k = 17;
+
+        let expected = Sha256::digest(b"abc");
+        let prover = MockProver::<F>::run(k, &circuit(b"abd"), vec![digest_words(&expected)]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(sha256_test_vectors, sha256_random_messages, sha256_wrong_digest);
+}
@@ -0,0 +1,234 @@
+use std::convert::TryInto;
+
+use crate::gadget::sha2::{Sha2Chip, Sha2Config, Sha2Constants};
+use crate::word::Word64;
+
+pub use crate::gadget::sha2::{BLOCK_SIZE, DIGEST_SIZE};
+
+const ROUNDS: usize = 80;
+
+const ROUND_CONSTANTS: [u64; ROUNDS] = [
+    0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc,
+    0x3956c25bf348b538, 0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118,
+    0xd807aa98a3030242, 0x12835b0145706fbe, 0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235, 0xc19bf174cf692694,
+    0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5,
+    0x983e5152ee66dfab, 0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2, 0xd5a79147930aa725, 0x06ca6351e003826f, 0x142929670a0e6e70,
+    0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed, 0x53380d139d95b3df,
+    0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+    0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30,
+    0xd192e819d6ef5218, 0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8, 0x1e376c085141ab53, 0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373, 0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+    0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b,
+    0xca273eceea26619c, 0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178,
+    0x06f067aa72176fba, 0x0a637dc5a2c898a6, 0x113f9804bef90dae, 0x1b710b35131c471b,
+    0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc, 0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+];
+
+/// FIPS 180-4 SHA-512 constants
+pub const SHA512: Sha2Constants = Sha2Constants {
+    iv: [
+        0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+        0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+    ],
+    round_constants: &ROUND_CONSTANTS,
+    big_sigma: [(28, 34, 39), (14, 18, 41)],
+    small_sigma: [(1, 8, 7), (19, 61, 6)],
+};
+
+/// Pads `message` and splits it into blocks of big-endian words
+pub fn pad(message: &[u8]) -> Vec<[Word64; BLOCK_SIZE]> {
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 128 != 112 {
+        padded.push(0);
+    }
+    padded.extend_from_slice(&((message.len() as u128) * 8).to_be_bytes());
+
+    padded
+        .chunks(128)
+        .map(|block| {
+            let words: Vec<Word64> = block
+                .chunks(8)
+                .map(|bytes| Word64::new(u64::from_be_bytes(bytes.try_into().unwrap())))
+                .collect();
+            words.try_into().unwrap()
+        })
+        .collect()
+}
+
+pub type Sha512Config = Sha2Config<64, 8>;
+pub type Sha512Chip<F> = Sha2Chip<F, 64, 8>;
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use sha2::{Digest, Sha512};
+    use std::convert::TryInto;
+
+    use super::{pad, Sha512Chip, Sha512Config, BLOCK_SIZE, SHA512};
+
+    use crate::gadget::{
+        bitwise::Bitwise64Chip,
+        decompose::Decompose64Chip,
+        xor::TableChip,
+    };
+    use crate::word::Word64;
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        sha512_config: Sha512Config
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        blocks: Vec<[Option<Word64>; BLOCK_SIZE]>
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                blocks: vec![[None; BLOCK_SIZE]; self.blocks.len()]
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_64_config = Decompose64Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_64_config = Bitwise64Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_64_config,
+                table_config
+            );
+            let sha512_config = Sha512Chip::configure(meta, bitwise_64_config, &SHA512);
+
+            Config {
+                instance,
+                sha512_config
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            TableChip::load(config.sha512_config.bitwise_config.table_config.clone(), &mut layouter)?;
+            let chip = Sha512Chip::<F>::construct(config.sha512_config.clone());
+
+            let blocks = self.blocks
+                .iter()
+                .enumerate()
+                .map(|(i, block)| chip.load_block(layouter.namespace(|| format!("load block {}", i)), *block))
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            let digest = chip.digest(layouter.namespace(|| "sha512"), &blocks)?;
+
+            for (row, word) in digest.iter().enumerate() {
+                layouter.constrain_instance(word.cell(), config.instance, row)?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn circuit(message: &[u8]) -> Circuit {
+        Circuit {
+            blocks: pad(message)
+                .into_iter()
+                .map(|block| block.map(Some))
+                .collect()
+        }
+    }
+
+    fn digest_words<F: FieldExt>(digest: &[u8]) -> Vec<F> {
+        digest
+            .chunks(8)
+            .map(|word| F::from(u64::from_be_bytes(word.try_into().unwrap())))
+            .collect()
+    }
+
+    fn hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn sha512_test_vectors<F: FieldExt>() {
+        let k = 17;
+
+        let vectors: [(&[u8], &str); 2] = [
+            (
+                b"",
+                "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce\
+                 47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"
+            ),
+            (
+                b"abc",
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                 2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+            ),
+        ];
+
+        for (message, expected) in vectors {
+            let prover = MockProver::<F>::run(k, &circuit(message), vec![digest_words(&hex(expected))]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn sha512_random_messages<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        // one and two block messages
+        for len in [rng.gen_range(0..112), rng.gen_range(112..240)] {
+            let message: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let expected = Sha512::digest(&message);
+
+            let prover = MockProver::<F>::run(k, &circuit(&message), vec![digest_words(&expected)]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn sha512_wrong_digest<F: FieldExt>() {
+        let k = 17;
+
+        let expected = Sha512::digest(b"abc");
+        let prover = MockProver::<F>::run(k, &circuit(b"abd"), vec![digest_words(&expected)]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(sha512_test_vectors, sha512_random_messages, sha512_wrong_digest);
+}
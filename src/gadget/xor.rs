@@ -1,16 +1,26 @@
 use halo2::{
     arithmetic::FieldExt,
     circuit::{Chip, Layouter, Region},
-    pasta::pallas,
     plonk::{Advice, Column, ConstraintSystem, Error, TableColumn},
-    poly::Rotation,
 };
-use std::convert::TryInto;
 use std::marker::PhantomData;
 
+use crate::gadget::lookup::LookupTable;
 use crate::word::{Chunk, AssignedChunk};
 
-const XOR_BITS: usize = 8;
+pub const XOR_BITS: usize = 8;
+
+fn xor_entry(row: usize) -> [u64; 3] {
+    let (l, r) = ((row >> XOR_BITS) as u64, (row & ((1 << XOR_BITS) - 1)) as u64);
+    [l, r, l ^ r]
+}
+
+/// Rows of (l, r, l ^ r) over every pair of chunks
+pub const XOR_TABLE: LookupTable = LookupTable {
+    name: "xor table",
+    len: 1 << (2 * XOR_BITS),
+    row: xor_entry,
+};
 
 /// An input word into a lookup, containing (tag, dense, spread)
 #[derive(Copy, Clone, Debug)]
@@ -52,6 +62,12 @@ pub struct Table {
     pub z: TableColumn,
 }
 
+impl Table {
+    pub fn columns(&self) -> [TableColumn; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TableConfig {
     pub input: Inputs,
@@ -84,21 +100,7 @@ impl<F: FieldExt> TableChip<F> {
         input_y: Column<Advice>,
         input_z: Column<Advice>,
     ) -> <Self as Chip<F>>::Config {
-        let table_x = meta.lookup_table_column();
-        let table_y = meta.lookup_table_column();
-        let table_z = meta.lookup_table_column();
-
-        meta.lookup(|meta| {
-            let x_cur = meta.query_advice(input_x, Rotation::cur());
-            let y_cur = meta.query_advice(input_y, Rotation::cur());
-            let z_cur = meta.query_advice(input_z, Rotation::cur());
-
-            vec![
-                (x_cur, table_x),
-                (y_cur, table_y),
-                (z_cur, table_z),
-            ]
-        });
+        let [table_x, table_y, table_z] = XOR_TABLE.configure(meta, [input_x, input_y, input_z], None);
 
         TableConfig {
             input: Inputs {
@@ -125,40 +127,7 @@ impl<F: FieldExt> TableChip<F> {
         config: TableConfig,
         layouter: &mut impl Layouter<F>,
     ) -> Result<<Self as Chip<F>>::Loaded, Error> {
-        let config = config.clone();
-        layouter.assign_table(
-            || "xor table",
-            |mut table| {
-                // We generate the row values lazily (we only need them during keygen).
-                // let mut rows = SpreadTableConfig::generate::<F>();
-
-                let mut row_offset = 0;
-                for l in 0..1 << XOR_BITS {
-                    for r in 0..1 << XOR_BITS {
-                        table.assign_cell(
-                            || format!("xor_l_col row {}", row_offset),
-                            config.table.x,
-                            row_offset,
-                            || Ok(F::from(l)),
-                        )?;
-                        table.assign_cell(
-                            || format!("xor_r_col row {}", row_offset),
-                            config.table.y,
-                            row_offset,
-                            || Ok(F::from(r)),
-                        )?;
-                        table.assign_cell(
-                            || format!("xor_o_col row {}", row_offset),
-                            config.table.z,
-                            row_offset,
-                            || Ok(F::from(l ^ r)),
-                        )?;
-                        row_offset += 1;
-                    }
-                }
-                Ok(())
-            },
-        )
+        XOR_TABLE.load(layouter, config.table.columns())
     }
 
     pub fn add_row(
@@ -203,98 +172,238 @@ impl<F: FieldExt> TableChip<F> {
 
         Ok(())
     }
+
+    /// Copies `x` and `y` into the lookup row and witnesses `z = x ^ y`.
+    pub fn xor_row(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        x: &AssignedChunk<F>,
+        y: &AssignedChunk<F>,
+    ) -> Result<AssignedChunk<F>, Error> {
+        let config = self.config();
+
+        x.copy(|| format!("x: {}", row), region, config.input.x, row)?;
+        y.copy(|| format!("y: {}", row), region, config.input.y, row)?;
+
+        let z = x.value_word().zip(y.value_word())
+            .map(|(x, y)| Chunk::new(*x ^ *y));
+
+        let assigned = region.assign_advice(
+            || format!("z: {}", row),
+            config.input.z,
+            row,
+            || z.ok_or(Error::Synthesis)
+        )?;
+
+        Ok(AssignedChunk::new(assigned))
+    }
+
+    /// Witnesses `x`, `y` and `z = x ^ y` in a lookup row, which range checks all three
+    pub fn witness_xor_row(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        x: Option<Chunk>,
+        y: Option<Chunk>,
+    ) -> Result<AssignedChunk<F>, Error> {
+        let config = self.config();
+
+        region.assign_advice(|| format!("x: {}", row), config.input.x, row, || x.ok_or(Error::Synthesis))?;
+        region.assign_advice(|| format!("y: {}", row), config.input.y, row, || y.ok_or(Error::Synthesis))?;
+
+        let z = x.zip(y).map(|(x, y)| Chunk::new(*x ^ *y));
+
+        let assigned = region.assign_advice(
+            || format!("z: {}", row),
+            config.input.z,
+            row,
+            || z.ok_or(Error::Synthesis)
+        )?;
+
+        Ok(AssignedChunk::new(assigned))
+    }
+
+    /// Like `xor_row`, with `y` fixed to a constant
+    pub fn xor_constant_row(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        x: &AssignedChunk<F>,
+        y: Chunk,
+    ) -> Result<AssignedChunk<F>, Error> {
+        let config = self.config();
+
+        x.copy(|| format!("x: {}", row), region, config.input.x, row)?;
+        region.assign_advice_from_constant(|| format!("y: {}", row), config.input.y, row, y)?;
+
+        let z = x.value_word().map(|x| Chunk::new(*x ^ *y));
+
+        let assigned = region.assign_advice(
+            || format!("z: {}", row),
+            config.input.z,
+            row,
+            || z.ok_or(Error::Synthesis)
+        )?;
+
+        Ok(AssignedChunk::new(assigned))
+    }
+
+    /// Witnesses a chunk in a lookup row, which range checks it to 8 bits
+    pub fn load_chunk(
+        &self,
+        region: &mut Region<'_, F>,
+        row: usize,
+        value: Option<Chunk>,
+    ) -> Result<AssignedChunk<F>, Error> {
+        let config = self.config();
+
+        let assigned = region.assign_advice(
+            || format!("x: {}", row),
+            config.input.x,
+            row,
+            || value.ok_or(Error::Synthesis)
+        )?;
+
+        region.assign_advice(
+            || format!("y: {}", row),
+            config.input.y,
+            row,
+            || Ok(Chunk::new(0))
+        )?;
+
+        region.assign_advice(
+            || format!("z: {}", row),
+            config.input.z,
+            row,
+            || value.ok_or(Error::Synthesis)
+        )?;
+
+        Ok(AssignedChunk::new(assigned))
+    }
+
+    /// Witnesses bytes in a region of `load_chunk` rows, which range checks them
+    pub fn load_chunks(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[Option<u8>],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        layouter.assign_region(
+            || "load bytes",
+            |mut region| {
+                bytes
+                    .iter()
+                    .enumerate()
+                    .map(|(row, byte)| self.load_chunk(&mut region, row, byte.map(|byte| Chunk::new(byte as u64))))
+                    .collect()
+            },
+        )
+    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::{TableChip, TableConfig};
-    use rand::Rng;
 
     use halo2::{
         arithmetic::FieldExt,
         circuit::{Layouter, SimpleFloorPlanner},
         dev::MockProver,
-        pasta::Fp,
-        plonk::{Advice, Circuit, Column, ConstraintSystem, Error},
+        plonk::{Circuit, ConstraintSystem, Error},
     };
 
+    use crate::tamper::Tampering;
     use crate::word::{Chunk};
 
-    use pasta_curves::pallas;
+    #[derive(Copy, Clone, Debug)]
+    struct MyCircuit {}
 
-    #[test]
-    fn lookup_table() {
-        #[derive(Copy, Clone, Debug)]
-        struct MyCircuit {}
+    impl<F: FieldExt> Circuit<F> for MyCircuit {
+        type Config = TableConfig;
+        type FloorPlanner = SimpleFloorPlanner;
 
-        impl Circuit<pallas::Base> for MyCircuit {
-            type Config = TableConfig;
-            type FloorPlanner = SimpleFloorPlanner;
+        fn without_witnesses(&self) -> Self {
+            MyCircuit {}
+        }
 
-            fn without_witnesses(&self) -> Self {
-                MyCircuit {}
-            }
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let input_x = meta.advice_column();
+            let input_y = meta.advice_column();
+            let input_z = meta.advice_column();
 
-            fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
-                let input_x = meta.advice_column();
-                let input_y = meta.advice_column();
-                let input_z = meta.advice_column();
+            let constants = meta.fixed_column();
+            meta.enable_constant(constants);
 
-                TableChip::configure(meta, input_x, input_y, input_z)
-            }
+            TableChip::configure(meta, input_x, input_y, input_z)
+        }
 
-            fn synthesize(
-                &self,
-                config: Self::Config,
-                mut layouter: impl Layouter<pallas::Base>,
-            ) -> Result<(), Error> {
-
-
-                TableChip::load(config.clone(), &mut layouter)?;
-
-                let table_chip = TableChip::construct(config);
-
-                layouter.assign_region(
-                    || "compress",
-                    |mut region| {
-                        table_chip.add_row(
-                            &mut region, 
-                            0, 
-                            Some(Chunk::new(0)), 
-                            Some(Chunk::new(1)), 
-                            Some(Chunk::new(1))
-                        )?;
-
-                        table_chip.add_row(
-                            &mut region, 
-                            1, 
-                            Some(Chunk::new(0b00110011)), 
-                            Some(Chunk::new(0b00110011)), 
-                            Some(Chunk::new(0b00000000))
-                        )?;
-
-                        table_chip.add_row(
-                            &mut region, 
-                            2, 
-                            Some(Chunk::new(0b01010101)), 
-                            Some(Chunk::new(0b10101010)), 
-                            Some(Chunk::new(0b11111111))
-                        )?;
-                        Ok(())
-                    },
-                )?;
-
-                Ok(())
-            }
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+
+            TableChip::load(config.clone(), &mut layouter)?;
+
+            let table_chip = TableChip::construct(config);
+
+            layouter.assign_region(
+                || "compress",
+                |mut region| {
+                    table_chip.add_row(
+                        &mut region, 
+                        0, 
+                        Some(Chunk::new(0)), 
+                        Some(Chunk::new(1)), 
+                        Some(Chunk::new(1))
+                    )?;
+
+                    table_chip.add_row(
+                        &mut region, 
+                        1, 
+                        Some(Chunk::new(0b00110011)), 
+                        Some(Chunk::new(0b00110011)), 
+                        Some(Chunk::new(0b00000000))
+                    )?;
+
+                    table_chip.add_row(
+                        &mut region, 
+                        2, 
+                        Some(Chunk::new(0b01010101)), 
+                        Some(Chunk::new(0b10101010)), 
+                        Some(Chunk::new(0b11111111))
+                    )?;
+                    Ok(())
+                },
+            )?;
+
+            layouter.assign_region(
+                || "rows",
+                |mut region| {
+                    let x = table_chip.witness_xor_row(&mut region, 0, Some(Chunk::new(0x12)), Some(Chunk::new(0x34)))?;
+                    let y = table_chip.load_chunk(&mut region, 1, Some(Chunk::new(0xf0)))?;
+                    let z = table_chip.xor_row(&mut region, 2, &x, &y)?;
+                    table_chip.xor_constant_row(&mut region, 3, &z, Chunk::new(0xff))?;
+                    Ok(())
+                },
+            )
         }
+    }
 
+    fn lookup_table<F: FieldExt>() {
         let circuit: MyCircuit = MyCircuit {};
 
-        let prover = match MockProver::<Fp>::run(17, &circuit, vec![]) {
+        let prover = match MockProver::<F>::run(17, &circuit, vec![]) {
             Ok(prover) => prover,
             Err(e) => panic!("{:?}", e),
         };
         assert_eq!(prover.verify(), Ok(()));
     }
+
+    fn lookup_table_tampering<F: FieldExt>() {
+        Tampering::<F, _>::new(17, &MyCircuit {}, vec![]).assert_sound();
+    }
+
+    pasta_tests!(lookup_table, lookup_table_tampering);
 }
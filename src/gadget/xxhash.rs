@@ -0,0 +1,362 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{Chip, Layouter},
+    plonk::{ConstraintSystem, Error},
+};
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use crate::gadget::{
+    bitwise::{Bitwise32Chip, Bitwise32Config, BitwiseInstructions},
+    xor::TableChip,
+};
+use crate::word::{AssignedChunk, AssignedWord32, Word32};
+
+const PRIME_1: u32 = 0x9e3779b1;
+const PRIME_2: u32 = 0x85ebca77;
+const PRIME_3: u32 = 0xc2b2ae3d;
+const PRIME_4: u32 = 0x27d4eb2f;
+const PRIME_5: u32 = 0x165667b1;
+
+/// `h ^= h >> shift` followed by `h *= multiplier` when there is one, for every step in turn.
+/// MurmurHash3's fmix is the same finalisation with other constants.
+pub fn avalanche<F: FieldExt>(
+    chip: &Bitwise32Chip<F>,
+    mut layouter: impl Layouter<F>,
+    mut h: AssignedWord32<F>,
+    steps: &[(u32, Option<Word32>)],
+) -> Result<AssignedWord32<F>, Error> {
+    for (i, (shift, multiplier)) in steps.iter().enumerate() {
+        let shifted = chip.shr(layouter.namespace(|| format!("shr {}", i)), &h, *shift)?;
+        h = chip.xor(layouter.namespace(|| format!("xor {}", i)), &h, &shifted)?;
+        if let Some(multiplier) = multiplier {
+            h = chip.mul_constant(layouter.namespace(|| format!("mul {}", i)), &h, *multiplier)?;
+        }
+    }
+
+    Ok(h)
+}
+
+/// Native XXH32, the reference for the chip
+pub fn xxhash32(bytes: &[u8], seed: u32) -> u32 {
+    let lane = |bytes: &[u8]| u32::from_le_bytes(bytes.try_into().unwrap());
+    let round = |acc: u32, lane: u32| acc.wrapping_add(lane.wrapping_mul(PRIME_2)).rotate_left(13).wrapping_mul(PRIME_1);
+
+    let stripes = bytes.chunks_exact(16);
+    let tail = stripes.remainder();
+
+    let mut h = if bytes.len() >= 16 {
+        let mut acc = [
+            seed.wrapping_add(PRIME_1).wrapping_add(PRIME_2),
+            seed.wrapping_add(PRIME_2),
+            seed,
+            seed.wrapping_sub(PRIME_1),
+        ];
+        for stripe in stripes {
+            for (i, acc) in acc.iter_mut().enumerate() {
+                *acc = round(*acc, lane(&stripe[4 * i..4 * i + 4]));
+            }
+        }
+        acc[0].rotate_left(1)
+            .wrapping_add(acc[1].rotate_left(7))
+            .wrapping_add(acc[2].rotate_left(12))
+            .wrapping_add(acc[3].rotate_left(18))
+    } else {
+        seed.wrapping_add(PRIME_5)
+    };
+
+    h = h.wrapping_add(bytes.len() as u32);
+
+    let words = tail.chunks_exact(4);
+    let rest = words.remainder();
+    for word in words {
+        h = h.wrapping_add(lane(word).wrapping_mul(PRIME_3)).rotate_left(17).wrapping_mul(PRIME_4);
+    }
+    for byte in rest {
+        h = h.wrapping_add((*byte as u32).wrapping_mul(PRIME_5)).rotate_left(11).wrapping_mul(PRIME_1);
+    }
+
+    h ^= h >> 15;
+    h = h.wrapping_mul(PRIME_2);
+    h ^= h >> 13;
+    h = h.wrapping_mul(PRIME_3);
+    h ^ (h >> 16)
+}
+
+#[derive(Clone, Debug)]
+pub struct XxHash32Config {
+    pub bitwise_config: Bitwise32Config,
+}
+
+#[derive(Clone, Debug)]
+pub struct XxHash32Chip<F> {
+    config: XxHash32Config,
+    _marker: PhantomData<F>,
+}
+
+impl<F: FieldExt> Chip<F> for XxHash32Chip<F> {
+    type Config = XxHash32Config;
+    type Loaded = ();
+
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    fn loaded(&self) -> &Self::Loaded {
+        &()
+    }
+}
+
+impl<F: FieldExt> XxHash32Chip<F> {
+    pub fn configure(
+        _meta: &mut ConstraintSystem<F>,
+        bitwise_config: Bitwise32Config,
+    ) -> XxHash32Config {
+        XxHash32Config {
+            bitwise_config
+        }
+    }
+
+    pub fn construct(config: XxHash32Config) -> Self {
+        XxHash32Chip {
+            config,
+            _marker: PhantomData
+        }
+    }
+
+    pub fn load(
+        config: XxHash32Config,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        TableChip::load(config.bitwise_config.table_config, layouter)
+    }
+
+    fn bitwise_chip(&self) -> Bitwise32Chip<F> {
+        Bitwise32Chip::construct(self.config.bitwise_config.clone())
+    }
+
+    fn table_chip(&self) -> TableChip<F> {
+        TableChip::construct(self.config.bitwise_config.table_config.clone())
+    }
+
+    /// Witnesses the input bytes, range checked through the xor table
+    pub fn load_bytes(
+        &self,
+        layouter: impl Layouter<F>,
+        bytes: &[Option<u8>],
+    ) -> Result<Vec<AssignedChunk<F>>, Error> {
+        self.table_chip().load_chunks(layouter, bytes)
+    }
+
+    /// XXH32 of range checked `bytes` with a fixed `seed`
+    pub fn hash(
+        &self,
+        mut layouter: impl Layouter<F>,
+        bytes: &[AssignedChunk<F>],
+        seed: u32,
+    ) -> Result<AssignedWord32<F>, Error> {
+        let chip = self.bitwise_chip();
+
+        let stripes = bytes.chunks_exact(16);
+        let tail = stripes.remainder();
+
+        let mut h = if bytes.len() >= 16 {
+            let mut acc = [
+                seed.wrapping_add(PRIME_1).wrapping_add(PRIME_2),
+                seed.wrapping_add(PRIME_2),
+                seed,
+                seed.wrapping_sub(PRIME_1),
+            ]
+            .iter()
+            .enumerate()
+            .map(|(i, acc)| chip.constant(layouter.namespace(|| format!("acc {}", i)), Word32::new(*acc as u64)))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+            for (i, stripe) in stripes.enumerate() {
+                let mut layouter = layouter.namespace(|| format!("stripe {}", i));
+                for (j, acc) in acc.iter_mut().enumerate() {
+                    let lane = chip.compose_le_padded(layouter.namespace(|| format!("lane {}", j)), &stripe[4 * j..4 * j + 4])?;
+                    *acc = self.round(layouter.namespace(|| format!("round {}", j)), acc, &lane, PRIME_2, 13, PRIME_1)?;
+                }
+            }
+
+            let mut h = chip.rotl(layouter.namespace(|| "converge 0"), &acc[0], 1)?;
+            for (i, n) in [(1, 7), (2, 12), (3, 18)] {
+                let rotated = chip.rotl(layouter.namespace(|| format!("converge {}", i)), &acc[i], n)?;
+                h = chip.add(layouter.namespace(|| format!("converge add {}", i)), &h, &rotated)?;
+            }
+            h
+        } else {
+            chip.constant(layouter.namespace(|| "seed"), Word32::new(seed.wrapping_add(PRIME_5) as u64))?
+        };
+
+        let len = chip.constant(layouter.namespace(|| "len"), Word32::new(bytes.len() as u64))?;
+        h = chip.add(layouter.namespace(|| "add len"), &h, &len)?;
+
+        let words = tail.chunks_exact(4);
+        let rest = words.remainder();
+        for (i, word) in words.enumerate() {
+            let lane = chip.compose_le_padded(layouter.namespace(|| format!("tail lane {}", i)), word)?;
+            h = self.round(layouter.namespace(|| format!("tail word {}", i)), &h, &lane, PRIME_3, 17, PRIME_4)?;
+        }
+        for (i, byte) in rest.iter().enumerate() {
+            let lane = chip.compose_le_padded(layouter.namespace(|| format!("tail byte lane {}", i)), std::slice::from_ref(byte))?;
+            h = self.round(layouter.namespace(|| format!("tail byte {}", i)), &h, &lane, PRIME_5, 11, PRIME_1)?;
+        }
+
+        let steps = [
+            (15, Some(Word32::new(PRIME_2 as u64))),
+            (13, Some(Word32::new(PRIME_3 as u64))),
+            (16, None),
+        ];
+        avalanche(&chip, layouter.namespace(|| "avalanche"), h, &steps)
+    }
+
+    /// rotl(acc + lane * prime_in, n) * prime_out
+    fn round(
+        &self,
+        mut layouter: impl Layouter<F>,
+        acc: &AssignedWord32<F>,
+        lane: &AssignedWord32<F>,
+        prime_in: u32,
+        n: u32,
+        prime_out: u32,
+    ) -> Result<AssignedWord32<F>, Error> {
+        let chip = self.bitwise_chip();
+
+        let product = chip.mul_constant(layouter.namespace(|| "lane * prime"), lane, Word32::new(prime_in as u64))?;
+        let sum = chip.add(layouter.namespace(|| "add"), acc, &product)?;
+        let rotated = chip.rotl(layouter.namespace(|| "rotl"), &sum, n)?;
+        chip.mul_constant(layouter.namespace(|| "rotated * prime"), &rotated, Word32::new(prime_out as u64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        dev::MockProver,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk,
+    };
+    use rand::Rng;
+    use std::convert::TryInto;
+
+    use super::{xxhash32, XxHash32Chip, XxHash32Config};
+
+    use crate::gadget::{
+        bitwise::Bitwise32Chip,
+        decompose::Decompose32Chip,
+        xor::TableChip,
+    };
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        instance: Column<Instance>,
+        xxhash_config: XxHash32Config,
+    }
+
+    #[derive(Debug, Default)]
+    pub struct Circuit {
+        bytes: Vec<Option<u8>>,
+        seed: u32,
+    }
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit {
+                bytes: vec![None; self.bytes.len()],
+                seed: self.seed,
+            }
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice: [Column<Advice>; 6] = [
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.advice_column(),
+            ];
+            let constants = meta.fixed_column();
+
+            let instance = meta.instance_column();
+            meta.enable_equality(instance.into());
+
+            let table_config = TableChip::configure(meta, advice[3], advice[4], advice[5]);
+            let decompose_config = Decompose32Chip::configure(meta, advice[0..3].try_into().unwrap());
+            let bitwise_config = Bitwise32Chip::configure(
+                meta,
+                advice[0..3].try_into().unwrap(),
+                constants,
+                decompose_config,
+                table_config
+            );
+            let xxhash_config = XxHash32Chip::configure(meta, bitwise_config);
+
+            Config {
+                instance,
+                xxhash_config,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            XxHash32Chip::<F>::load(config.xxhash_config.clone(), &mut layouter)?;
+            let chip = XxHash32Chip::<F>::construct(config.xxhash_config.clone());
+
+            let bytes = chip.load_bytes(layouter.namespace(|| "bytes"), &self.bytes)?;
+            let hash = chip.hash(layouter.namespace(|| "xxhash32"), &bytes, self.seed)?;
+
+            layouter.constrain_instance(hash.cell(), config.instance, 0)
+        }
+    }
+
+    fn circuit(bytes: &[u8], seed: u32) -> Circuit {
+        Circuit {
+            bytes: bytes.iter().copied().map(Some).collect(),
+            seed,
+        }
+    }
+
+    #[test]
+    fn native_xxhash32() {
+        assert_eq!(xxhash32(b"", 0), 0x02cc5d05);
+        assert_eq!(xxhash32(b"abc", 0), 0x32d153ff);
+        assert_eq!(xxhash32(b"The quick brown fox jumps over the lazy dog", 0), 0xe85ea4de);
+        assert_eq!(xxhash32(b"hello", 1), 0xfcfffba9);
+    }
+
+    fn xxhash32_test<F: FieldExt>() {
+        let k = 17;
+        let mut rng = rand::thread_rng();
+
+        // short input, and a stripe followed by a tail word and tail bytes
+        let long: Vec<u8> = (0..23).map(|_| rng.gen()).collect();
+        let inputs: [(&[u8], u32); 3] = [(b"", 0), (b"abc", 1), (&long, rng.gen())];
+
+        for (bytes, seed) in inputs {
+            let expected = F::from(xxhash32(bytes, seed) as u64);
+            let prover = MockProver::<F>::run(k, &circuit(bytes, seed), vec![vec![expected]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()));
+        }
+    }
+
+    fn xxhash32_wrong_hash<F: FieldExt>() {
+        let k = 17;
+
+        let expected = F::from(xxhash32(b"abc", 0) as u64);
+        let prover = MockProver::<F>::run(k, &circuit(b"abd", 0), vec![vec![expected]]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    pasta_tests!(xxhash32_test, xxhash32_wrong_hash);
+}
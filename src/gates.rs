@@ -16,32 +16,321 @@ impl<F: FieldExt> Gate<F> {
         expr
     }
 
-    pub fn decompose_32_to_8(
+    // x = sum(chunks[i] * 2^(8 * i)) with every chunk a byte, chunks are little-endian
+    // and callers reorder them for big-endian layouts
+    pub fn decompose(
         q_decompose: Expression<F>,
         x: Expression<F>,
-        x0: Expression<F>,
-        x1: Expression<F>,
-        x2: Expression<F>,
-        x3: Expression<F>
+        chunks: Vec<Expression<F>>
     ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let decomposition_check = chunks
+        .iter()
+        .enumerate()
+        .fold(x * (-F::one()), |acc, (i, chunk)| acc + chunk.clone() * F::from_u128(1 << (8 * i)));
 
-        let decomposition_check = x0.clone()
-        + x1.clone() * F::from(1 << 8)
-        + x2.clone() * F::from(1 << 16)
-        + x3.clone() * F::from(1 << 24)
-        + x * (-F::one());
-
-        let range_check_x0 = Self::range_check(x0, 0, 255);
-        let range_check_x1 = Self::range_check(x1, 0, 255);
-        let range_check_x2 = Self::range_check(x2, 0, 255);
-        let range_check_x3 = Self::range_check(x3, 0, 255);
+        let range_checks = chunks
+        .into_iter()
+        .map(|chunk| ("range_check_chunk", Self::range_check(chunk, 0, 255)));
 
         std::iter::empty()
         .chain(Some(("decomposition_check", decomposition_check)))
-        .chain(Some(("range_check_x0", range_check_x0)))
-        .chain(Some(("range_check_x1", range_check_x1)))
-        .chain(Some(("range_check_x2", range_check_x2)))
-        .chain(Some(("range_check_x3", range_check_x3)))
+        .chain(range_checks)
         .map(move |(name, poly)| (name, q_decompose.clone() * poly))
     }
-}
\ No newline at end of file
+
+    // x = sum(chunks[i] * 2^(8 * i)), the chunks are range checked by the caller
+    pub fn compose(
+        q_compose: Expression<F>,
+        x: Expression<F>,
+        chunks: Vec<Expression<F>>
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let composition_check = chunks
+        .iter()
+        .enumerate()
+        .fold(x * (-F::one()), |acc, (i, chunk)| acc + chunk.clone() * F::from_u128(1 << (8 * i)));
+
+        std::iter::empty()
+        .chain(Some(("composition_check", composition_check)))
+        .map(move |(name, poly)| (name, q_compose.clone() * poly))
+    }
+
+    // x = lo + hi * 2^half_bits, lo and hi are range checked by the caller
+    pub fn join(
+        q_join: Expression<F>,
+        x: Expression<F>,
+        lo: Expression<F>,
+        hi: Expression<F>,
+        half_bits: u32
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let composition_check = lo
+        + hi * F::from_u128(1 << half_bits)
+        + x * (-F::one());
+
+        std::iter::empty()
+        .chain(Some(("composition_check", composition_check)))
+        .map(move |(name, poly)| (name, q_join.clone() * poly))
+    }
+
+    // a + b = (a ^ b) + 2 * (a & b)
+    pub fn and(
+        q_and: Expression<F>,
+        a: Expression<F>,
+        b: Expression<F>,
+        xor: Expression<F>,
+        out: Expression<F>
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let and_check = a + b + xor * (-F::one()) + out * (-F::from(2u64));
+
+        std::iter::empty()
+        .chain(Some(("and_check", and_check)))
+        .map(move |(name, poly)| (name, q_and.clone() * poly))
+    }
+
+    // a + b + (a ^ b) = 2 * (a | b)
+    pub fn or(
+        q_or: Expression<F>,
+        a: Expression<F>,
+        b: Expression<F>,
+        xor: Expression<F>,
+        out: Expression<F>
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let or_check = a + b + xor + out * (-F::from(2u64));
+
+        std::iter::empty()
+        .chain(Some(("or_check", or_check)))
+        .map(move |(name, poly)| (name, q_or.clone() * poly))
+    }
+
+    // !a = 2^bits - 1 - a
+    pub fn not(
+        q_not: Expression<F>,
+        a: Expression<F>,
+        out: Expression<F>,
+        bits: u32
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let not_check = a + out + Self::identity() * (-F::from_u128((1 << bits) - 1));
+
+        std::iter::empty()
+        .chain(Some(("not_check", not_check)))
+        .map(move |(name, poly)| (name, q_not.clone() * poly))
+    }
+
+    // a + b = out + carry * 2^bits, out is range checked by the caller
+    pub fn add_with_carry(
+        q_add: Expression<F>,
+        a: Expression<F>,
+        b: Expression<F>,
+        out: Expression<F>,
+        carry: Expression<F>,
+        bits: u32
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let sum_check = a + b
+        + out * (-F::one())
+        + carry.clone() * (-F::from_u128(1 << bits));
+
+        let range_check_carry = Self::range_check(carry, 0, 1);
+
+        std::iter::empty()
+        .chain(Some(("sum_check", sum_check)))
+        .chain(Some(("range_check_carry", range_check_carry)))
+        .map(move |(name, poly)| (name, q_add.clone() * poly))
+    }
+
+    // w * 2^n = hi * 2^bits + lo and out = lo + hi, where `shift` holds 2^n.
+    // hi_shifted = hi * 2^(bits - n) is range checked by the caller to bound hi by 2^n,
+    // lo and out are range checked by the caller as well.
+    #[allow(clippy::too_many_arguments)]
+    pub fn rotate_left(
+        q_rotl: Expression<F>,
+        shift: Expression<F>,
+        w: Expression<F>,
+        hi: Expression<F>,
+        lo: Expression<F>,
+        out: Expression<F>,
+        hi_shifted: Expression<F>,
+        bits: u32
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let split_check = w * shift.clone()
+        + hi.clone() * (-F::from_u128(1 << bits))
+        + lo.clone() * (-F::one());
+
+        let rotation_check = lo + hi.clone() + out * (-F::one());
+
+        let hi_shifted_check = hi_shifted * shift
+        + hi * (-F::from_u128(1 << bits));
+
+        std::iter::empty()
+        .chain(Some(("split_check", split_check)))
+        .chain(Some(("rotation_check", rotation_check)))
+        .chain(Some(("hi_shifted_check", hi_shifted_check)))
+        .map(move |(name, poly)| (name, q_rotl.clone() * poly))
+    }
+
+    // w = hi * 2^n + lo, where `shift` holds 2^n and hi is the result.
+    // lo_shifted = lo * 2^(bits - n) is range checked by the caller to bound lo by 2^n,
+    // hi is range checked by the caller as well.
+    pub fn shift_right(
+        q_shr: Expression<F>,
+        shift: Expression<F>,
+        w: Expression<F>,
+        hi: Expression<F>,
+        lo: Expression<F>,
+        lo_shifted: Expression<F>,
+        bits: u32
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let split_check = hi * shift.clone()
+        + lo.clone()
+        + w * (-F::one());
+
+        let lo_shifted_check = lo_shifted * shift
+        + lo * (-F::from_u128(1 << bits));
+
+        std::iter::empty()
+        .chain(Some(("split_check", split_check)))
+        .chain(Some(("lo_shifted_check", lo_shifted_check)))
+        .map(move |(name, poly)| (name, q_shr.clone() * poly))
+    }
+
+    // out = 1 - (a - b) * inv and (a - b) * out = 0, so out is 1 exactly when a = b
+    pub fn is_equal(
+        q_eq: Expression<F>,
+        a: Expression<F>,
+        b: Expression<F>,
+        inv: Expression<F>,
+        out: Expression<F>
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let diff = a + b * (-F::one());
+
+        let inverse_check = diff.clone() * inv + out.clone() + Self::identity() * (-F::one());
+        let zero_check = diff * out;
+
+        std::iter::empty()
+        .chain(Some(("inverse_check", inverse_check)))
+        .chain(Some(("zero_check", zero_check)))
+        .map(move |(name, poly)| (name, q_eq.clone() * poly))
+    }
+
+    // a - b + borrow * 2^bits = diff, diff is range checked by the caller so borrow is 1 exactly when a < b
+    pub fn less_than(
+        q_lt: Expression<F>,
+        a: Expression<F>,
+        b: Expression<F>,
+        borrow: Expression<F>,
+        diff: Expression<F>,
+        bits: u32
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let borrow_check = a
+        + b * (-F::one())
+        + borrow.clone() * F::from_u128(1 << bits)
+        + diff * (-F::one());
+
+        let range_check_borrow = Self::range_check(borrow, 0, 1);
+
+        std::iter::empty()
+        .chain(Some(("borrow_check", borrow_check)))
+        .chain(Some(("range_check_borrow", range_check_borrow)))
+        .map(move |(name, poly)| (name, q_lt.clone() * poly))
+    }
+
+    // a * b = lo + hi * 2^bits, lo and hi are range checked by the caller.
+    // With a, b < 2^bits the product is below 2^(2 * bits) < p, so it cannot wrap.
+    pub fn mul_wide(
+        q_mul: Expression<F>,
+        a: Expression<F>,
+        b: Expression<F>,
+        lo: Expression<F>,
+        hi: Expression<F>,
+        bits: u32
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let product_check = a * b
+        + lo * (-F::one())
+        + hi * (-F::from_u128(1 << bits));
+
+        std::iter::empty()
+        .chain(Some(("product_check", product_check)))
+        .map(move |(name, poly)| (name, q_mul.clone() * poly))
+    }
+
+    // acc = acc_prev * 256 + byte, one step of packing bytes into a field element
+    pub fn pack(
+        q_pack: Expression<F>,
+        acc_prev: Expression<F>,
+        acc: Expression<F>,
+        byte: Expression<F>
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let pack_check = acc_prev * F::from(256u64)
+        + byte
+        + acc * (-F::one());
+
+        std::iter::empty()
+        .chain(Some(("pack_check", pack_check)))
+        .map(move |(name, poly)| (name, q_pack.clone() * poly))
+    }
+
+    // out = cond * a + (1 - cond) * b with cond boolean
+    pub fn select(
+        q_select: Expression<F>,
+        cond: Expression<F>,
+        a: Expression<F>,
+        b: Expression<F>,
+        out: Expression<F>
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let select_check = cond.clone() * (a + b.clone() * (-F::one()))
+        + b
+        + out * (-F::one());
+
+        let range_check_cond = Self::range_check(cond, 0, 1);
+
+        std::iter::empty()
+        .chain(Some(("select_check", select_check)))
+        .chain(Some(("range_check_cond", range_check_cond)))
+        .map(move |(name, poly)| (name, q_select.clone() * poly))
+    }
+
+    // acc = acc_prev + count and parity_acc = parity_acc_prev ^ parity, for boolean parities
+    pub fn popcount(
+        q_popcount: Expression<F>,
+        count: Expression<F>,
+        parity: Expression<F>,
+        acc_prev: Expression<F>,
+        acc: Expression<F>,
+        parity_acc_prev: Expression<F>,
+        parity_acc: Expression<F>
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let count_check = acc_prev + count + acc * (-F::one());
+
+        let parity_check = parity_acc_prev.clone()
+        + parity.clone()
+        + parity_acc_prev * parity * (-F::from(2u64))
+        + parity_acc * (-F::one());
+
+        std::iter::empty()
+        .chain(Some(("count_check", count_check)))
+        .chain(Some(("parity_check", parity_check)))
+        .map(move |(name, poly)| (name, q_popcount.clone() * poly))
+    }
+
+    // x, y and z are each the sum of their little-endian chunks, the xor table already
+    // range checks the chunks and relates them so the compositions are all that is left
+    pub fn fused_xor(
+        q_fused: Expression<F>,
+        words: [Expression<F>; 3],
+        chunks: [Vec<Expression<F>>; 3]
+    ) -> impl Iterator<Item = (&'static str, Expression<F>)> {
+        let [x, y, z] = words;
+        let [x_chunks, y_chunks, z_chunks] = chunks;
+
+        let composition = |word: Expression<F>, chunks: Vec<Expression<F>>| {
+            chunks
+            .into_iter()
+            .enumerate()
+            .fold(word * (-F::one()), |acc, (i, chunk)| acc + chunk * F::from_u128(1 << (8 * i)))
+        };
+
+        std::iter::empty()
+        .chain(Some(("x_composition", composition(x, x_chunks))))
+        .chain(Some(("y_composition", composition(y, y_chunks))))
+        .chain(Some(("z_composition", composition(z, z_chunks))))
+        .map(move |(name, poly)| (name, q_fused.clone() * poly))
+    }
+}
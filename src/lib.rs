@@ -1,8 +1,39 @@
 // temp while in dev
 #![allow(dead_code)]
 
+/// Runs tests generic over the field on the base fields of both Pasta curves
+#[cfg(test)]
+macro_rules! pasta_tests {
+    ($($test:ident),* $(,)?) => {
+        mod pallas_base {
+            $(
+                #[test]
+                fn $test() {
+                    super::$test::<pasta_curves::pallas::Base>()
+                }
+            )*
+        }
+
+        mod vesta_base {
+            $(
+                #[test]
+                fn $test() {
+                    super::$test::<pasta_curves::vesta::Base>()
+                }
+            )*
+        }
+    };
+}
+
 pub mod circuit;
+pub mod cost;
+pub mod format;
 pub mod utils;
 pub mod word;
 pub mod gadget;
-pub mod gates;
\ No newline at end of file
+pub mod gates;
+pub mod prover;
+/// Test tooling: soundness checks for circuits built from these gadgets, run on `MockProver`.
+/// Built for this crate's tests, other crates enable the `tamper` feature.
+#[cfg(any(test, feature = "tamper"))]
+pub mod tamper;
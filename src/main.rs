@@ -0,0 +1,284 @@
+use halo2::{
+    pasta::EqAffine,
+    plonk::{self, keygen_vk, VerifyingKey},
+    poly::commitment::Params,
+};
+use std::{
+    env, fmt,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter},
+    path::{Path, PathBuf},
+    process,
+};
+
+use xors::{
+    circuit::{self, Circuit},
+    cost::RowBudget,
+    format::{self, FormatError, Header, Kind},
+    prover::{self, Setup},
+};
+
+const USAGE: &str = "\
+usage: xors setup [--k <k>] [--dir <dir>]
+       xors prove (--x <word> --y <word> | --witness <file>) [--k <k>] [--dir <dir>] [--proof <file>] [--public <file>]
+       xors verify (--z <word> | --public <file>) [--k <k>] [--dir <dir>] [--proof <file>]
+
+Words are decimal or 0x prefixed hex, a witness file holds x and y separated by whitespace.
+Exit codes: 0 success, 1 invalid proof, 2 usage, 3 configuration, 4 i/o.";
+
+#[derive(Debug)]
+enum CliError {
+    InvalidProof,
+    Usage(String),
+    Config(String),
+    Io(io::Error),
+}
+
+impl CliError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::InvalidProof => 1,
+            CliError::Usage(_) => 2,
+            CliError::Config(_) => 3,
+            CliError::Io(_) => 4,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::InvalidProof => write!(f, "invalid proof"),
+            CliError::Usage(msg) => write!(f, "{}\n\n{}", msg, USAGE),
+            CliError::Config(msg) => write!(f, "{}", msg),
+            CliError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        CliError::Io(err)
+    }
+}
+
+impl From<FormatError> for CliError {
+    fn from(err: FormatError) -> Self {
+        match err {
+            FormatError::Io(err) => CliError::Io(err),
+            err => CliError::Config(err.to_string()),
+        }
+    }
+}
+
+impl From<plonk::Error> for CliError {
+    fn from(err: plonk::Error) -> Self {
+        CliError::Config(format!("{:?}", err))
+    }
+}
+
+impl From<prover::ProverError> for CliError {
+    fn from(err: prover::ProverError) -> Self {
+        CliError::Config(err.to_string())
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    k: Option<u32>,
+    dir: Option<PathBuf>,
+    proof: Option<PathBuf>,
+    public: Option<PathBuf>,
+    witness: Option<PathBuf>,
+    x: Option<u32>,
+    y: Option<u32>,
+    z: Option<u32>,
+}
+
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, CliError> {
+        let mut parsed = Args::default();
+
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| CliError::Usage(format!("missing value for {}", flag)))?;
+
+            match flag.as_str() {
+                "--k" => parsed.k = Some(value.parse().map_err(|_| CliError::Usage(format!("invalid k {}", value)))?),
+                "--dir" => parsed.dir = Some(value.into()),
+                "--proof" => parsed.proof = Some(value.into()),
+                "--public" => parsed.public = Some(value.into()),
+                "--witness" => parsed.witness = Some(value.into()),
+                "--x" => parsed.x = Some(parse_word(&value)?),
+                "--y" => parsed.y = Some(parse_word(&value)?),
+                "--z" => parsed.z = Some(parse_word(&value)?),
+                _ => return Err(CliError::Usage(format!("unknown flag {}", flag))),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn k(&self) -> u32 {
+        self.k.unwrap_or(circuit::K)
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.clone().unwrap_or_else(|| PathBuf::from(".")).join(name)
+    }
+
+    fn proof(&self) -> PathBuf {
+        self.proof.clone().unwrap_or_else(|| self.path("proof.bin"))
+    }
+
+    fn public(&self) -> PathBuf {
+        self.public.clone().unwrap_or_else(|| self.path("public.txt"))
+    }
+}
+
+fn parse_word(value: &str) -> Result<u32, CliError> {
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| CliError::Usage(format!("invalid word {}", value)))
+}
+
+fn read_words(path: &Path) -> Result<Vec<u32>, CliError> {
+    fs::read_to_string(path)?
+        .split_whitespace()
+        .map(|word| parse_word(word).map_err(|_| CliError::Config(format!("invalid word {} in {}", word, path.display()))))
+        .collect()
+}
+
+fn load_keys(args: &Args) -> Result<(Params<EqAffine>, VerifyingKey<EqAffine>), CliError> {
+    let k = args.k();
+
+    let params = format::read_params(&mut BufReader::new(File::open(args.path("params.bin"))?), Header::xor(Kind::Params, k))?;
+    let vk = format::read_vk(
+        &mut BufReader::new(File::open(args.path("vk.bin"))?),
+        Header::xor(Kind::VerifyingKey, k),
+        &params,
+        &Circuit::default(),
+    )?;
+
+    Ok((params, vk))
+}
+
+fn setup(args: &Args) -> Result<(), CliError> {
+    let k = args.k();
+    RowBudget::measure(&Circuit::default())?
+        .check(k)
+        .map_err(|err| CliError::Config(err.to_string()))?;
+
+    let params = Params::new(k);
+    let vk = keygen_vk(&params, &Circuit::default())?;
+
+    if let Some(dir) = &args.dir {
+        fs::create_dir_all(dir)?;
+    }
+    format::write_params(&mut BufWriter::new(File::create(args.path("params.bin"))?), Header::xor(Kind::Params, k), &params)?;
+    format::write_vk(&mut BufWriter::new(File::create(args.path("vk.bin"))?), Header::xor(Kind::VerifyingKey, k), &vk)?;
+
+    Ok(())
+}
+
+fn prove(args: &Args) -> Result<(), CliError> {
+    let (x, y) = match (&args.witness, args.x, args.y) {
+        (Some(witness), None, None) => match read_words(witness)?[..] {
+            [x, y] => (x, y),
+            _ => return Err(CliError::Config(format!("{} must hold exactly x and y", witness.display()))),
+        },
+        (None, Some(x), Some(y)) => (x, y),
+        _ => return Err(CliError::Usage("prove takes either --x and --y or --witness".to_string())),
+    };
+
+    let (params, vk) = load_keys(args)?;
+    let setup = Setup::load(params, vk, &Circuit::default())?;
+    let proof = setup.prove(x, y)?;
+
+    let z = x ^ y;
+    format::write_proof(&mut BufWriter::new(File::create(args.proof())?), Header::xor(Kind::Proof, args.k()), &proof)?;
+    fs::write(args.public(), format!("{:#010x}\n", z))?;
+    println!("{:#010x}", z);
+
+    Ok(())
+}
+
+fn verify(args: &Args) -> Result<(), CliError> {
+    let z = match (args.z, &args.public) {
+        (Some(z), None) => z,
+        (None, _) => match read_words(&args.public())?[..] {
+            [z] => z,
+            _ => return Err(CliError::Config(format!("{} must hold exactly z", args.public().display()))),
+        },
+        _ => return Err(CliError::Usage("verify takes either --z or --public".to_string())),
+    };
+
+    let (params, vk) = load_keys(args)?;
+    let proof = format::read_proof(&mut BufReader::new(File::open(args.proof())?), Header::xor(Kind::Proof, args.k()))?;
+
+    if !prover::verify(&params, &vk, &proof, &[&Circuit::public_inputs(z)]) {
+        return Err(CliError::InvalidProof);
+    }
+    println!("ok");
+
+    Ok(())
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), CliError> {
+    let command = args.next().ok_or_else(|| CliError::Usage("missing command".to_string()))?;
+    let args = Args::parse(args)?;
+
+    match command.as_str() {
+        "setup" => setup(&args),
+        "prove" => prove(&args),
+        "verify" => verify(&args),
+        _ => Err(CliError::Usage(format!("unknown command {}", command))),
+    }
+}
+
+fn main() {
+    if let Err(err) = run(env::args().skip(1)) {
+        eprintln!("xors: {}", err);
+        process::exit(err.exit_code());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_word, run, Args};
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(parse_word("0xdeadbeef").unwrap(), 0xdeadbeef);
+        assert_eq!(parse_word("42").unwrap(), 42);
+        assert!(parse_word("0x100000000").is_err());
+
+        let parsed = Args::parse(args(&["--x", "1", "--y", "0x2", "--k", "18"])).unwrap();
+        assert_eq!((parsed.x, parsed.y, parsed.k()), (Some(1), Some(2), 18));
+    }
+
+    #[test]
+    fn exit_codes() {
+        assert_eq!(run(args(&["frobnicate"])).unwrap_err().exit_code(), 2);
+        assert_eq!(run(args(&["prove", "--x", "1"])).unwrap_err().exit_code(), 2);
+        assert_eq!(run(args(&["verify", "--z", "1", "--dir", "/nonexistent"])).unwrap_err().exit_code(), 4);
+
+        // unique to this test run, concurrent runs must not share the bogus params
+        let dir = std::env::temp_dir().join(format!("xors-exit-codes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("params.bin"), b"not params").unwrap();
+        let verify = run(args(&["verify", "--z", "1", "--dir", dir.to_str().unwrap()]));
+        let setup = run(args(&["setup", "--k", "16", "--dir", dir.to_str().unwrap()]));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(verify.unwrap_err().exit_code(), 3);
+        assert_eq!(setup.unwrap_err().exit_code(), 3);
+    }
+}
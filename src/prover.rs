@@ -0,0 +1,174 @@
+use halo2::{
+    pasta::{EqAffine, Fp},
+    plonk::{self, create_proof, keygen_pk, keygen_vk, verify_proof, Error, ProvingKey, SingleVerifier, VerifyingKey},
+    poly::commitment::Params,
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+};
+use rand::rngs::OsRng;
+use std::fmt;
+
+use crate::circuit::Circuit;
+use crate::cost::{self, KTooSmall, RowBudget};
+
+#[derive(Debug)]
+pub enum ProverError {
+    Halo2(Error),
+    /// The circuit does not fit the rows of the params, caught before halo2 runs out of rows
+    KTooSmall(KTooSmall),
+}
+
+impl fmt::Display for ProverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProverError::Halo2(err) => write!(f, "{:?}", err),
+            ProverError::KTooSmall(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for ProverError {}
+
+impl From<Error> for ProverError {
+    fn from(err: Error) -> Self {
+        ProverError::Halo2(err)
+    }
+}
+
+impl From<KTooSmall> for ProverError {
+    fn from(err: KTooSmall) -> Self {
+        ProverError::KTooSmall(err)
+    }
+}
+
+/// Params and keys of a circuit, over the Pasta IPA commitment scheme
+#[derive(Debug)]
+pub struct Setup {
+    pub params: Params<EqAffine>,
+    pub pk: ProvingKey<EqAffine>,
+}
+
+impl Setup {
+    /// Generates fresh params for `2^k` rows and the keys of `circuit`,
+    /// only the shape of `circuit` matters so it can be without witnesses
+    pub fn new<C: plonk::Circuit<Fp>>(k: u32, circuit: &C) -> Result<Self, ProverError> {
+        RowBudget::measure(circuit)?.check(k)?;
+
+        let params = Params::new(k);
+        let pk = keygen(&params, circuit)?;
+
+        Ok(Setup { params, pk })
+    }
+
+    /// Setup from stored params and verifying key, only the proving key is regenerated
+    pub fn load<C: plonk::Circuit<Fp>>(params: Params<EqAffine>, vk: VerifyingKey<EqAffine>, circuit: &C) -> Result<Self, ProverError> {
+        check_rows(&params, circuit)?;
+        let pk = keygen_pk(&params, vk, circuit)?;
+
+        Ok(Setup { params, pk })
+    }
+
+    pub fn vk(&self) -> &VerifyingKey<EqAffine> {
+        self.pk.get_vk()
+    }
+
+    /// Setup of the xor circuit at the smallest k it fits
+    pub fn xor() -> Result<Self, ProverError> {
+        let circuit = Circuit::default();
+        Self::new(cost::min_k(&circuit)?, &circuit)
+    }
+
+    /// Proves `x ^ y` with the xor circuit
+    pub fn prove(&self, x: u32, y: u32) -> Result<Vec<u8>, ProverError> {
+        prove(&self.params, &self.pk, Circuit::new(x, y), &[&Circuit::public_inputs(x ^ y)])
+    }
+
+    /// Checks a proof of the xor circuit against the public `z`
+    pub fn verify(&self, proof: &[u8], public_inputs: &[Fp]) -> bool {
+        verify(&self.params, self.vk(), proof, &[public_inputs])
+    }
+}
+
+/// Fails with `KTooSmall` where halo2 would report `NotEnoughRowsAvailable`
+fn check_rows<C: plonk::Circuit<Fp>>(params: &Params<EqAffine>, circuit: &C) -> Result<(), ProverError> {
+    let k = params.get_g().len().trailing_zeros();
+    RowBudget::measure(circuit)?.check(k)?;
+    Ok(())
+}
+
+pub fn keygen<C: plonk::Circuit<Fp>>(params: &Params<EqAffine>, circuit: &C) -> Result<ProvingKey<EqAffine>, ProverError> {
+    check_rows(params, circuit)?;
+
+    let vk = keygen_vk(params, circuit)?;
+    Ok(keygen_pk(params, vk, circuit)?)
+}
+
+/// Proves a single circuit, `instances` holds the values of each instance column
+pub fn prove<C: plonk::Circuit<Fp>>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    instances: &[&[Fp]],
+) -> Result<Vec<u8>, ProverError> {
+    check_rows(params, &circuit)?;
+
+    let mut transcript = Blake2bWrite::<_, _, Challenge255<_>>::init(vec![]);
+    create_proof(params, pk, &[circuit], &[instances], OsRng, &mut transcript)?;
+
+    Ok(transcript.finalize())
+}
+
+/// False for malformed proofs as well as for proofs that do not verify
+pub fn verify(
+    params: &Params<EqAffine>,
+    vk: &VerifyingKey<EqAffine>,
+    proof: &[u8],
+    instances: &[&[Fp]],
+) -> bool {
+    let strategy = SingleVerifier::new(params);
+    let mut transcript = Blake2bRead::<_, _, Challenge255<_>>::init(proof);
+
+    verify_proof(params, vk, strategy, &[instances], &mut transcript).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ProverError, Setup};
+
+    use crate::circuit::{self, Circuit};
+
+    #[test]
+    fn prove_and_verify() {
+        let setup = Setup::xor().unwrap();
+        let (x, y) = (0xdeadbeef, 0x01234567);
+
+        let proof = setup.prove(x, y).unwrap();
+        assert!(setup.verify(&proof, &Circuit::public_inputs(x ^ y)));
+
+        // the same proof does not hold for any other output
+        assert!(!setup.verify(&proof, &Circuit::public_inputs(x ^ y ^ 1)));
+    }
+
+    #[test]
+    fn k_too_small() {
+        let err = Setup::new(circuit::K - 1, &Circuit::default()).unwrap_err();
+        assert!(matches!(err, ProverError::KTooSmall(_)), "{:?}", err);
+        assert!(err.to_string().ends_with("the smallest k is 17"), "{}", err);
+    }
+
+    #[test]
+    fn tampered_proof() {
+        let setup = Setup::xor().unwrap();
+        let (x, y) = (0xdeadbeef, 0x01234567);
+
+        let proof = setup.prove(x, y).unwrap();
+        let public_inputs = Circuit::public_inputs(x ^ y);
+
+        for i in [0, proof.len() / 2, proof.len() - 1] {
+            let mut tampered = proof.clone();
+            tampered[i] ^= 1;
+            assert!(!setup.verify(&tampered, &public_inputs));
+        }
+
+        assert!(!setup.verify(&proof[..proof.len() - 1], &public_inputs));
+    }
+}
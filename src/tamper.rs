@@ -0,0 +1,377 @@
+use halo2::{
+    arithmetic::FieldExt,
+    circuit::{layouter::RegionLayouter, Cell, Layouter, Region, Table},
+    dev::MockProver,
+    plonk::{self, Advice, Assigned, Column, ConstraintSystem, Error, Fixed, Instance, Selector},
+};
+use std::{cell::RefCell, fmt};
+
+/// How a tampered advice cell differs from its honest value
+#[derive(Clone, Copy, Debug)]
+pub enum Change<F> {
+    Add(F),
+    Set(F),
+}
+
+impl<F: FieldExt> Change<F> {
+    fn apply(&self, value: Assigned<F>) -> Assigned<F> {
+        match self {
+            Change::Add(delta) => value + Assigned::from(*delta),
+            Change::Set(value) => Assigned::from(*value),
+        }
+    }
+}
+
+/// An advice cell the circuit assigned a value to, numbered in assignment order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdviceCell {
+    pub index: usize,
+    pub region: String,
+    pub annotation: String,
+}
+
+impl fmt::Display for AdviceCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {:?} in region {:?}", self.index, self.annotation, self.region)
+    }
+}
+
+/// Records the assigned advice cells and changes the one at `target`
+#[derive(Debug)]
+struct Tamper<F> {
+    target: Option<(usize, Change<F>)>,
+    cells: RefCell<Vec<AdviceCell>>,
+}
+
+impl<F: FieldExt> Tamper<F> {
+    fn assign(&self, region: &str, annotation: String, value: Assigned<F>) -> Assigned<F> {
+        let mut cells = self.cells.borrow_mut();
+        let index = cells.len();
+        cells.push(AdviceCell {
+            index,
+            region: region.to_string(),
+            annotation,
+        });
+
+        match self.target {
+            Some((target, change)) if target == index => change.apply(value),
+            _ => value,
+        }
+    }
+}
+
+/// Region that passes every assignment through a `Tamper`
+#[derive(Debug)]
+struct TamperRegion<'r, 't, F: FieldExt> {
+    region: Region<'r, F>,
+    name: &'t str,
+    tamper: &'t Tamper<F>,
+}
+
+impl<F: FieldExt> RegionLayouter<F> for TamperRegion<'_, '_, F> {
+    fn enable_selector<'v>(
+        &'v mut self,
+        _: &'v (dyn Fn() -> String + 'v),
+        selector: &Selector,
+        offset: usize,
+    ) -> Result<(), Error> {
+        selector.enable(&mut self.region, offset)
+    }
+
+    fn assign_advice<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Advice>,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Result<Assigned<F>, Error> + 'v),
+    ) -> Result<Cell, Error> {
+        let (name, tamper) = (self.name, self.tamper);
+        let assigned = self.region.assign_advice(annotation, column, offset, || {
+            Ok(tamper.assign(name, annotation(), to()?))
+        })?;
+        Ok(assigned.cell())
+    }
+
+    fn assign_advice_from_constant<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Advice>,
+        offset: usize,
+        constant: Assigned<F>,
+    ) -> Result<Cell, Error> {
+        let assigned = self.region.assign_advice_from_constant(annotation, column, offset, constant)?;
+        Ok(assigned.cell())
+    }
+
+    fn assign_advice_from_instance<'v>(
+        &mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        instance: Column<Instance>,
+        row: usize,
+        advice: Column<Advice>,
+        offset: usize,
+    ) -> Result<(Cell, Option<F>), Error> {
+        let assigned = self.region.assign_advice_from_instance(annotation, instance, row, advice, offset)?;
+        Ok((assigned.cell(), assigned.value().copied()))
+    }
+
+    fn assign_fixed<'v>(
+        &'v mut self,
+        annotation: &'v (dyn Fn() -> String + 'v),
+        column: Column<Fixed>,
+        offset: usize,
+        to: &'v mut (dyn FnMut() -> Result<Assigned<F>, Error> + 'v),
+    ) -> Result<Cell, Error> {
+        let assigned = self.region.assign_fixed(annotation, column, offset, to)?;
+        Ok(assigned.cell())
+    }
+
+    fn constrain_constant(&mut self, cell: Cell, constant: Assigned<F>) -> Result<(), Error> {
+        self.region.constrain_constant(cell, constant)
+    }
+
+    fn constrain_equal(&mut self, left: Cell, right: Cell) -> Result<(), Error> {
+        self.region.constrain_equal(left, right)
+    }
+}
+
+/// Layouter that hands the circuit `TamperRegion`s, tables and instances are left alone
+#[derive(Debug)]
+struct TamperLayouter<'t, F: FieldExt, L: Layouter<F>> {
+    inner: L,
+    tamper: &'t Tamper<F>,
+}
+
+impl<F: FieldExt, L: Layouter<F>> Layouter<F> for TamperLayouter<'_, F, L> {
+    type Root = Self;
+
+    fn assign_region<A, AR, N, NR>(&mut self, name: N, mut assignment: A) -> Result<AR, Error>
+    where
+        A: FnMut(Region<'_, F>) -> Result<AR, Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        let tamper = self.tamper;
+        let region_name: String = name().into();
+
+        self.inner.assign_region(name, |region| {
+            let mut region = TamperRegion {
+                region,
+                name: &region_name,
+                tamper,
+            };
+            let region: &mut dyn RegionLayouter<F> = &mut region;
+            assignment(region.into())
+        })
+    }
+
+    fn assign_table<A, N, NR>(&mut self, name: N, assignment: A) -> Result<(), Error>
+    where
+        A: FnMut(Table<'_, F>) -> Result<(), Error>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        self.inner.assign_table(name, assignment)
+    }
+
+    fn constrain_instance(&mut self, cell: Cell, column: Column<Instance>, row: usize) -> Result<(), Error> {
+        self.inner.constrain_instance(cell, column, row)
+    }
+
+    fn get_root(&mut self) -> &mut Self::Root {
+        self
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.inner.get_root().push_namespace(name_fn)
+    }
+
+    fn pop_namespace(&mut self, gadget_name: Option<String>) {
+        self.inner.get_root().pop_namespace(gadget_name)
+    }
+}
+
+/// `circuit` laid out through a `TamperLayouter`, only meant for `MockProver`
+#[derive(Debug)]
+struct Tampered<'c, F: FieldExt, C> {
+    circuit: &'c C,
+    tamper: Tamper<F>,
+}
+
+impl<F: FieldExt, C: plonk::Circuit<F>> plonk::Circuit<F> for Tampered<'_, F, C> {
+    type Config = C::Config;
+    type FloorPlanner = C::FloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Tampered {
+            circuit: self.circuit,
+            tamper: Tamper {
+                target: None,
+                cells: RefCell::new(vec![]),
+            },
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        C::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        let layouter = TamperLayouter {
+            inner: layouter,
+            tamper: &self.tamper,
+        };
+        self.circuit.synthesize(config, layouter)
+    }
+}
+
+/// Soundness harness: reruns a circuit that verifies with one advice cell changed at a time
+/// and reports the changes `MockProver::verify` does not catch.
+#[derive(Debug)]
+pub struct Tampering<'c, F: FieldExt, C> {
+    k: u32,
+    circuit: &'c C,
+    instance: Vec<Vec<F>>,
+    cells: Vec<AdviceCell>,
+}
+
+impl<'c, F: FieldExt, C: plonk::Circuit<F>> Tampering<'c, F, C> {
+    /// Panics unless the honest circuit verifies
+    pub fn new(k: u32, circuit: &'c C, instance: Vec<Vec<F>>) -> Self {
+        let honest = Tampered {
+            circuit,
+            tamper: Tamper {
+                target: None,
+                cells: RefCell::new(vec![]),
+            },
+        };
+
+        let prover = MockProver::run(k, &honest, instance.clone()).unwrap();
+        assert_eq!(prover.verify(), Ok(()), "the honest circuit does not verify");
+
+        Tampering {
+            k,
+            circuit,
+            instance,
+            cells: honest.tamper.cells.into_inner(),
+        }
+    }
+
+    /// Advice cells assigned with a witness, constants and copies from instances are not included
+    pub fn cells(&self) -> &[AdviceCell] {
+        &self.cells
+    }
+
+    /// Whether verification fails with the cell at `index` changed
+    pub fn detects(&self, index: usize, change: Change<F>) -> bool {
+        let tampered = Tampered {
+            circuit: self.circuit,
+            tamper: Tamper {
+                target: Some((index, change)),
+                cells: RefCell::new(vec![]),
+            },
+        };
+
+        // a change that breaks synthesis itself cannot make it into a proof either
+        match MockProver::run(self.k, &tampered, self.instance.clone()) {
+            Ok(prover) => prover.verify().is_err(),
+            Err(_) => true,
+        }
+    }
+
+    /// Panics listing every cell among `cells` whose `change` verifies
+    pub fn assert_detected<'a>(&self, cells: impl IntoIterator<Item = &'a AdviceCell>, change: Change<F>) {
+        let undetected: Vec<String> = cells
+            .into_iter()
+            .filter(|cell| !self.detects(cell.index, change))
+            .map(|cell| cell.to_string())
+            .collect();
+
+        assert!(undetected.is_empty(), "tampering goes unnoticed for {}", undetected.join(", "));
+    }
+
+    /// Adds one to every assigned advice cell in turn, each has to fail verification
+    pub fn assert_sound(&self) {
+        self.assert_detected(self.cells.iter(), Change::Add(F::one()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use halo2::{
+        arithmetic::FieldExt,
+        circuit::{Layouter, SimpleFloorPlanner},
+        plonk::{self, Advice, Column, ConstraintSystem, Error, Instance},
+    };
+
+    use super::{AdviceCell, Change, Tampering};
+
+    #[derive(Clone, Debug)]
+    pub struct Config {
+        advice: Column<Advice>,
+        instance: Column<Instance>,
+    }
+
+    /// Exposes its first cell and leaves the second one unconstrained
+    #[derive(Debug, Default)]
+    pub struct Circuit;
+
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
+        type Config = Config;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Circuit
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let advice = meta.advice_column();
+            let instance = meta.instance_column();
+            meta.enable_equality(advice.into());
+            meta.enable_equality(instance.into());
+
+            Config { advice, instance }
+        }
+
+        fn synthesize(&self, config: Self::Config, mut layouter: impl Layouter<F>) -> Result<(), Error> {
+            let exposed = layouter.assign_region(
+                || "cells",
+                |mut region| {
+                    let exposed = region.assign_advice(|| "exposed", config.advice, 0, || Ok(F::from(7)))?;
+                    region.assign_advice(|| "free", config.advice, 1, || Ok(F::from(8)))?;
+                    Ok(exposed)
+                },
+            )?;
+
+            layouter.constrain_instance(exposed.cell(), config.instance, 0)
+        }
+    }
+
+    fn harness<F: FieldExt>() {
+        let harness = Tampering::new(4, &Circuit, vec![vec![F::from(7)]]);
+
+        let cell = |index, annotation: &str| AdviceCell {
+            index,
+            region: "cells".to_string(),
+            annotation: annotation.to_string(),
+        };
+        assert_eq!(harness.cells(), [cell(0, "exposed"), cell(1, "free")]);
+
+        assert!(harness.detects(0, Change::Add(F::one())));
+        assert!(harness.detects(0, Change::Set(F::zero())));
+        assert!(!harness.detects(0, Change::Set(F::from(7))));
+        assert!(!harness.detects(1, Change::Add(F::one())));
+    }
+
+    #[test]
+    #[should_panic(expected = "tampering goes unnoticed for #1 \"free\" in region \"cells\"")]
+    fn harness_reports_free_cells() {
+        Tampering::new(4, &Circuit, vec![vec![pasta_curves::pallas::Base::from(7)]]).assert_sound();
+    }
+
+    pasta_tests!(harness);
+}
@@ -1,127 +1,188 @@
 use halo2::{
     arithmetic::FieldExt,
-    circuit::{Layouter, Region, AssignedCell},
-    plonk::{Column, Advice, Error, Instance, Assigned},
-    circuit
+    circuit::{Layouter, Region, AssignedCell, Cell},
+    plonk::{Column, Advice, Error, Assigned},
 };
 
-use pasta_curves::pallas;
+/// Unsigned integer of `BITS` bits, at most 64.
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
+pub struct Word<const BITS: usize>(u64);
 
-#[derive(Clone, Debug, Copy, Default)]
-pub struct Chunk(u8);
+pub type Bit = Word<1>;
+pub type Chunk = Word<8>;
+pub type Word16 = Word<16>;
+pub type Word32 = Word<32>;
+pub type Word64 = Word<64>;
 
-impl Chunk {
-    pub fn new(x: u8) -> Self {
-        return Chunk(x) 
+impl<const BITS: usize> Word<BITS> {
+    pub const MASK: u64 = if BITS == 64 { u64::MAX } else { (1 << BITS) - 1 };
+
+    pub fn new(x: u64) -> Self {
+        assert!(BITS <= 64 && x <= Self::MASK, "{} does not fit in {} bits", x, BITS);
+        Word(x)
+    }
+
+    /// Splits into `LIMBS` little-endian limbs of `LIMB_BITS` bits each
+    pub fn decompose<const LIMB_BITS: usize, const LIMBS: usize>(&self) -> [Word<LIMB_BITS>; LIMBS] {
+        assert_eq!(LIMB_BITS * LIMBS, BITS);
+
+        let mut limbs = [Word(0); LIMBS];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = Word((self.0 >> (i * LIMB_BITS)) & Word::<LIMB_BITS>::MASK);
+        }
+        limbs
+    }
+
+    /// Inverse of `decompose`
+    pub fn compose<const LIMB_BITS: usize, const LIMBS: usize>(limbs: [Word<LIMB_BITS>; LIMBS]) -> Self {
+        assert_eq!(LIMB_BITS * LIMBS, BITS);
+
+        let composed = limbs
+            .iter()
+            .rev()
+            .fold(0u128, |acc, limb| acc << LIMB_BITS | limb.0 as u128);
+        Word(composed as u64)
+    }
+
+    /// `decompose` with the limbs in the given order
+    pub fn decompose_with<const LIMB_BITS: usize, const LIMBS: usize>(&self, endianness: Endianness) -> [Word<LIMB_BITS>; LIMBS] {
+        let mut limbs = self.decompose();
+        if endianness == Endianness::Big {
+            limbs.reverse();
+        }
+        limbs
+    }
+
+    /// `compose` of limbs in the given order
+    pub fn compose_with<const LIMB_BITS: usize, const LIMBS: usize>(mut limbs: [Word<LIMB_BITS>; LIMBS], endianness: Endianness) -> Self {
+        if endianness == Endianness::Big {
+            limbs.reverse();
+        }
+        Self::compose(limbs)
     }
+
+    /// Reverses the byte order, `BITS` must be a multiple of 8
+    pub fn swap_bytes(self) -> Self {
+        assert_eq!(BITS % 8, 0);
+
+        let swapped = (0..BITS / 8)
+            .fold(0, |acc, i| acc << 8 | (self.0 >> (8 * i)) & 0xff);
+        Word(swapped)
+    }
+
+    pub fn rotate_left(self, n: u32) -> Self {
+        let n = n as usize % BITS;
+        if n == 0 {
+            return self;
+        }
+        Word((self.0 << n | self.0 >> (BITS - n)) & Self::MASK)
+    }
+
+    pub fn rotate_right(self, n: u32) -> Self {
+        self.rotate_left((BITS - n as usize % BITS) as u32)
+    }
+
+    pub fn wrapping_add(self, other: Self) -> Self {
+        Word(((self.0 as u128 + other.0 as u128) & Self::MASK as u128) as u64)
+    }
+}
+
+/// Order of the limbs of a word, or of the bytes of a byte string
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
 }
 
-impl std::ops::Deref for Chunk {
-    type Target = u8;
+impl<const BITS: usize> std::ops::Deref for Word<BITS> {
+    type Target = u64;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct AssignedChunk(AssignedCell<Chunk, pallas::Base>);
+impl<const BITS: usize> std::ops::Not for Word<BITS> {
+    type Output = Self;
 
-impl AssignedChunk {
-    pub fn new(assigned_cell: AssignedCell<Chunk, pallas::Base>) -> Self {
-        AssignedChunk(assigned_cell)
+    fn not(self) -> Self {
+        Word(!self.0 & Self::MASK)
     }
+}
+
+impl<const BITS: usize> std::ops::BitXor for Word<BITS> {
+    type Output = Self;
 
-    pub fn value_chunk(&self) -> Option<Chunk> {
-        self.0.value().map(|v| *v)
+    fn bitxor(self, other: Self) -> Self {
+        Word(self.0 ^ other.0)
     }
 }
 
-impl From<&Chunk> for Assigned<pallas::Base> {
-    fn from(chunk: &Chunk) -> Assigned<pallas::Base> {
-        pallas::Base::from(chunk.0 as u64).into()
+impl<const BITS: usize> std::ops::BitAnd for Word<BITS> {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self {
+        Word(self.0 & other.0)
     }
 }
 
+impl<const BITS: usize> std::ops::BitOr for Word<BITS> {
+    type Output = Self;
 
-impl AssignedChunk {
-    fn assign_chunk(
-        mut layouter: impl Layouter<pallas::Base>,
-        column: Column<Advice>,
-        value: Option<Chunk>,
-    ) -> Result<AssignedChunk, Error> {
-        layouter.assign_region(
-            || "witness word",
-            |mut region| {
-                let assigned = region.assign_advice(
-                    || "witness",
-                    column,
-                    0,
-                    || value.ok_or(Error::Synthesis),
-                )?;
-                Ok(AssignedChunk::new(assigned))
-            },
-        )
+    fn bitor(self, other: Self) -> Self {
+        Word(self.0 | other.0)
     }
 }
 
+impl<const BITS: usize> std::ops::Shl<u32> for Word<BITS> {
+    type Output = Self;
 
-#[derive(Clone, Debug, Copy, Default)]
-pub struct Word(u32);
-
-impl Word {
-    pub fn new(x: u32) -> Self {
-        return Word(x) 
+    fn shl(self, n: u32) -> Self {
+        Word((self.0 << n) & Self::MASK)
     }
+}
 
-    pub fn decompose_4(&self) -> [Chunk; 4] {
-        let bytes = self.to_le_bytes();
-        bytes.map(|byte| Chunk(byte))
-    }
+impl<const BITS: usize> std::ops::Shr<u32> for Word<BITS> {
+    type Output = Self;
 
-    pub fn compose(chunks: [u8; 4]) -> Self {
-        return Word(u32::from_le_bytes(chunks))
+    fn shr(self, n: u32) -> Self {
+        Word(self.0 >> n)
     }
 }
 
-impl std::ops::Deref for Word {
-    type Target = u32;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
+impl<F: FieldExt, const BITS: usize> From<&Word<BITS>> for Assigned<F> {
+    fn from(word: &Word<BITS>) -> Assigned<F> {
+        F::from(word.0).into()
     }
 }
 
 #[derive(Clone, Debug)]
-pub struct AssignedWord(AssignedCell<Word, pallas::Base>);
+pub struct AssignedWord<F: FieldExt, const BITS: usize>(AssignedCell<Word<BITS>, F>);
+
+pub type AssignedBit<F> = AssignedWord<F, 1>;
+pub type AssignedChunk<F> = AssignedWord<F, 8>;
+pub type AssignedWord16<F> = AssignedWord<F, 16>;
+pub type AssignedWord32<F> = AssignedWord<F, 32>;
+pub type AssignedWord64<F> = AssignedWord<F, 64>;
 
-impl AssignedWord {
-    pub fn new(assigned_cell: AssignedCell<Word, pallas::Base>) -> Self {
+impl<F: FieldExt, const BITS: usize> AssignedWord<F, BITS> {
+    pub fn new(assigned_cell: AssignedCell<Word<BITS>, F>) -> Self {
         AssignedWord(assigned_cell)
     }
 
-    pub fn value_word(&self) -> Option<Word> {
-        self.0.value().map(|word| *word)
+    pub fn value_word(&self) -> Option<Word<BITS>> {
+        self.0.value().copied()
     }
 
-    // pub fn decompose_4(&self) -> [Option<Chunk>; 4] {
-    //     self.0.value().map(|word| )
-    // }
-}
-
-impl From<&Word> for Assigned<pallas::Base> {
-    fn from(word: &Word) -> Assigned<pallas::Base> {
-        pallas::Base::from(word.0 as u64).into()
+    pub fn cell(&self) -> Cell {
+        self.0.cell()
     }
-}
-
 
-impl AssignedWord {
     pub fn assign_word(
-        mut layouter: impl Layouter<pallas::Base>,
+        mut layouter: impl Layouter<F>,
         column: Column<Advice>,
-        value: Option<Word>,
-    ) -> Result<AssignedWord, Error> {
+        value: Option<Word<BITS>>,
+    ) -> Result<Self, Error> {
         layouter.assign_region(
             || "witness word",
             |mut region| {
@@ -139,7 +200,7 @@ impl AssignedWord {
     pub fn copy<A, AR>(
         &self,
         annotation: A,
-        region: &mut Region<'_, pallas::Base>,
+        region: &mut Region<'_, F>,
         column: Column<Advice>,
         offset: usize,
     ) -> Result<Self, Error>
@@ -153,42 +214,30 @@ impl AssignedWord {
     }
 }
 
-// impl<F: FieldExt> AssignedCell<F>
-// {
-// }
-
-
 #[cfg(test)]
 mod test {
 
     use halo2::{
+        arithmetic::FieldExt,
         circuit::{Layouter, SimpleFloorPlanner},
-        plonk::{Advice, Instance, Column, ConstraintSystem, Error},
+        plonk::{Advice, Column, ConstraintSystem, Error},
         plonk,
-        pasta::Fp,
         dev::MockProver,
     };
-    use std::marker::PhantomData;
-    use pasta_curves::pallas;
 
-    use super::{Word, AssignedWord};
+    use super::{AssignedWord32, Chunk, Endianness, Word32, Word64};
 
     #[derive(Clone, Debug)]
     pub struct Config {
         advice: [Column<Advice>; 4],
-        // instance: Column<Instance>,
     }
 
     #[derive(Clone, Debug, Default)]
     pub struct Circuit {
-        value: Option<Word>
+        value: Option<Word32>
     }
 
-    // impl UtilitiesInstructions<pallas::Base> for Circuit {
-    //     type Var = NumericCell<pallas::Base>;
-    // }
-
-    impl plonk::Circuit<pallas::Base> for Circuit {
+    impl<F: FieldExt> plonk::Circuit<F> for Circuit {
         type Config = Config;
         type FloorPlanner = SimpleFloorPlanner;
 
@@ -196,7 +245,7 @@ mod test {
             Self::default()
         }
 
-        fn configure(meta: &mut ConstraintSystem<pallas::Base>) -> Self::Config {
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
 
             let advice = [
                 meta.advice_column(),
@@ -205,39 +254,68 @@ mod test {
                 meta.advice_column()
             ];
 
-            // let instance = meta.instance_column();
-            // meta.enable_equality(instance.into());
-
             for advice in advice.iter() {
                 meta.enable_equality((*advice).into());
             }
 
             Config {
-                advice, 
-                // instance,
+                advice,
             }
         }
 
         fn synthesize(
             &self,
             config: Self::Config,
-            mut layouter: impl Layouter<pallas::Base>,
+            mut layouter: impl Layouter<F>,
         ) -> Result<(), Error> {
             let config = config.clone();
 
-            let assigned = AssignedWord::assign_word(layouter.namespace(|| "witness value"), config.advice[0], self.value).unwrap();
+            AssignedWord32::assign_word(layouter.namespace(|| "witness value"), config.advice[0], self.value)?;
             Ok(())
         }
     }
 
-    #[test]
-    fn assign_word() {
-        let value = Word::new(5);
+    fn assign_word<F: FieldExt>() {
+        let value = Word32::new(5);
         let circuit = Circuit {
             value: Some(value)
         };
         let k = 4;
-        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        let prover = MockProver::<F>::run(k, &circuit, vec![]).unwrap();
         assert_eq!(prover.verify(), Ok(()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn decompose_compose() {
+        let word = Word64::new(0x0123456789abcdef);
+
+        let bytes: [Chunk; 8] = word.decompose();
+        assert_eq!(bytes.map(|byte| *byte as u8), 0x0123456789abcdefu64.to_le_bytes());
+        assert_eq!(Word64::compose(bytes), word);
+
+        let [lo, hi] = word.decompose::<32, 2>();
+        assert_eq!((*lo, *hi), (0x89abcdef, 0x01234567));
+        assert_eq!(Word64::compose([lo, hi]), word);
+
+        let bytes: [Chunk; 8] = word.decompose_with(Endianness::Big);
+        assert_eq!(bytes.map(|byte| *byte as u8), 0x0123456789abcdefu64.to_be_bytes());
+        assert_eq!(Word64::compose_with(bytes, Endianness::Big), word);
+
+        assert_eq!(*word.swap_bytes(), 0xefcdab8967452301);
+        assert_eq!(*Word32::new(0x12345678).swap_bytes(), 0x78563412);
+    }
+
+    #[test]
+    fn masked_operations() {
+        let a = Word32::new(0x80000001);
+
+        assert_eq!(*!a, 0x7ffffffe);
+        assert_eq!(*a.rotate_left(1), 0x00000003);
+        assert_eq!(*a.rotate_right(1), 0xc0000000);
+        assert_eq!(*(a << 1), 0x00000002);
+        assert_eq!(*a.wrapping_add(a), 0x00000002);
+        assert_eq!(*Chunk::new(0xff).rotate_left(3), 0xff);
+    }
+
+    pasta_tests!(assign_word);
+}